@@ -52,6 +52,12 @@ CREDENTIAL_ID=$(kiltctl util asset-dids get-credential-id --subject ${ASSET_DID}
 echo "Credential ID: ${CREDENTIAL_ID}"
 kiltctl storage public-credentials credentials --asset-did ${ASSET_DID} --credential-id ${CREDENTIAL_ID}
 
+echo "List all credentials of the asset DID"
+kiltctl storage public-credentials credentials --asset-did ${ASSET_DID}
+
+echo "Look up the subject of the credential by its id"
+kiltctl --output json storage public-credentials credentials --credential-id ${CREDENTIAL_ID} | jq -r .subject
+
 echo "Revoke the public credential..."
 kiltctl tx public-credentials revoke --id ${CREDENTIAL_ID} | \
     kiltctl tx did authorize --did "${ATTESTER_DID}" --seed "${ATTESTER_ATTESTATION_SEED}" --submitter "${SUBMITTER_ACCOUNT}" | \
@@ -1,5 +1,11 @@
 use chrono::Utc;
-use kiltapi::{connect, credential::CredentialBuilder, unwrap_or_stdin};
+use kiltapi::{
+    connect,
+    credential::CredentialBuilder,
+    ctype::{self, CType, CTypeRefs},
+    print_output, storage_at, unwrap_or_stdin,
+};
+use subxt::ext::sp_core::H256;
 
 pub fn command() -> clap::Command {
     clap::Command::new("create")
@@ -25,6 +31,18 @@ pub fn command() -> clap::Command {
                 .help("ctype to use for the credential")
                 .env("CTYPE"),
         )
+        .arg(
+            clap::Arg::new("ctype-file")
+                .long("ctype-file")
+                .action(clap::ArgAction::Append)
+                .help("CType definition file (repeat for referenced CTypes, others are fetched)"),
+        )
+        .arg(
+            clap::Arg::new("skip-validation")
+                .long("skip-validation")
+                .action(clap::ArgAction::SetTrue)
+                .help("do not validate the claims against the CType"),
+        )
         .arg(
             clap::Arg::new("issuer")
                 .long("issuer")
@@ -44,15 +62,55 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let ctype = matches.get_one::<String>("ctype").unwrap();
     let claims = unwrap_or_stdin(matches.get_one::<String>("claims").map(|e| e.to_owned()))?;
     let issuer = matches.get_one::<String>("issuer");
+    let mut claim_map: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&claims)?;
+
+    let mut cli = None;
+    if !matches.get_flag("skip-validation") {
+        let mut known = CTypeRefs::new();
+        let mut storage = None;
+        for file in matches.get_many::<String>("ctype-file").unwrap_or_default() {
+            let def: CType = serde_json::from_str(&std::fs::read_to_string(file)?)?;
+            known.insert(def.id()?, def);
+        }
+
+        // load the credential's CType and everything it references
+        let id = format!("kilt:ctype:{}", ctype.trim_start_matches("kilt:ctype:"));
+        let mut pending = vec![id.clone()];
+        while let Some(next) = pending.pop() {
+            if !known.contains_key(&next) {
+                let hash: [u8; 32] = hex::decode(next.trim_start_matches("kilt:ctype:0x"))
+                    .ok()
+                    .and_then(|hash| hash.try_into().ok())
+                    .ok_or_else(|| format!("invalid ctype id {next}"))?;
+                if cli.is_none() {
+                    let client = connect(matches).await?;
+                    storage = Some(storage_at(matches, &client).await?);
+                    cli = Some(client);
+                }
+                let def =
+                    ctype::fetch(cli.as_ref().unwrap(), storage.as_ref().unwrap(), H256(hash))
+                        .await?;
+                known.insert(next.clone(), def);
+            }
+            pending.extend(known[&next].references());
+        }
+
+        known[&id]
+            .validate(&claim_map, &known)
+            .map_err(|e| format!("claims do not match the CType: {e}"))?;
+    }
+
     let block = match matches.get_one::<String>("block") {
         Some(block) => block.to_owned(),
         None => {
-            let cli = connect(matches).await?;
+            let cli = match cli {
+                Some(cli) => cli,
+                None => connect(matches).await?,
+            };
             cli.blocks().at_latest().await.unwrap().number().to_string()
         }
     };
 
-    let mut claim_map: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&claims)?;
     claim_map.insert("@id".into(), subject.clone().into());
     let mut builder = CredentialBuilder::new()
         .with_ctype(ctype)
@@ -66,7 +124,11 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
 
     let cred = builder.create_proof(&block)?.build()?;
 
-    println!("{}", serde_json::to_string_pretty(&cred)?);
+    print_output(
+        matches,
+        serde_json::to_string_pretty(&cred)?,
+        serde_json::to_value(&cred)?,
+    );
 
     Ok(())
 }
@@ -0,0 +1,62 @@
+use clap::ArgAction;
+use kiltapi::{credential::Credential, print_output, unwrap_or_stdin};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("disclose")
+        .about("Derive a presentation that only reveals the selected claims")
+        .arg(
+            clap::Arg::new("credential")
+                .short('c')
+                .long("credential")
+                .help("credential to derive from")
+                .env("CREDENTIAL"),
+        )
+        .arg(
+            clap::Arg::new("keep")
+                .short('k')
+                .long("keep")
+                .help("claim to reveal; all other claims are hidden")
+                .action(ArgAction::Append)
+                .required(true),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let keep = matches
+        .get_many::<String>("keep")
+        .unwrap()
+        .map(|e| e.as_str())
+        .collect::<Vec<_>>();
+
+    let credential = unwrap_or_stdin(
+        matches
+            .get_one::<String>("credential")
+            .map(|e| e.to_owned()),
+    )?;
+    let cred: Credential = serde_json::from_str(&credential)?;
+
+    for name in &keep {
+        if !cred.credential_subject.contains_key(*name) {
+            return Err(format!("unknown claim {name}").into());
+        }
+    }
+
+    // @-keys like @id and @context are never hidden
+    let hide = cred
+        .credential_subject
+        .keys()
+        .filter(|key| !key.starts_with('@') && !keep.contains(&key.as_str()))
+        .map(|key| key.as_str())
+        .collect::<Vec<_>>();
+
+    let derived = cred.derive(&hide)?;
+    derived.verify()?;
+
+    print_output(
+        matches,
+        serde_json::to_string_pretty(&derived)?,
+        serde_json::to_value(&derived)?,
+    );
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use base58::FromBase58;
-use kiltapi::{credential::Credential, unwrap_or_stdin};
+use kiltapi::{credential::Credential, print_output, unwrap_or_stdin};
 
 pub fn command() -> clap::Command {
     clap::Command::new("hash")
@@ -28,6 +28,11 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         .from_base58()
         .map_err(|_| "failed to parse id")?;
 
-    println!("0x{}", hex::encode(root_hash));
+    let root_hash = format!("0x{}", hex::encode(root_hash));
+    print_output(
+        matches,
+        &root_hash,
+        serde_json::json!({ "hash": root_hash }),
+    );
     Ok(())
 }
@@ -1,12 +1,22 @@
 mod create;
+mod disclose;
 mod hash;
+mod present;
 mod verify;
+mod verify_presentation;
 
 pub fn command() -> clap::Command {
     clap::Command::new("credential")
         .about("Credential commands")
         .subcommand_required(true)
-        .subcommands([create::command(), verify::command(), hash::command()])
+        .subcommands([
+            create::command(),
+            verify::command(),
+            hash::command(),
+            disclose::command(),
+            present::command(),
+            verify_presentation::command(),
+        ])
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
@@ -14,6 +24,9 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         Some(("create", matches)) => create::run(matches).await,
         Some(("verify", matches)) => verify::run(matches).await,
         Some(("hash", matches)) => hash::run(matches).await,
+        Some(("disclose", matches)) => disclose::run(matches).await,
+        Some(("present", matches)) => present::run(matches).await,
+        Some(("verify-presentation", matches)) => verify_presentation::run(matches).await,
         _ => Ok(()),
     }
 }
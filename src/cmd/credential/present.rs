@@ -0,0 +1,112 @@
+use chrono::Utc;
+use clap::ArgAction;
+use kiltapi::{
+    connect,
+    credential::{Credential, Presentation},
+    did::{self, did_uri, public_key},
+    kilt::runtime_types::did::did_details::DidPublicKey,
+    print_output, resolve_did, storage_at, unwrap_or_stdin, DidArg, DidParser,
+};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("present")
+        .about("Wrap credentials in a presentation signed by the holder DID")
+        .arg(
+            clap::Arg::new("credential")
+                .short('c')
+                .long("credential")
+                .help("credential to present; can be repeated (reads a credential or a list of credentials from stdin if omitted)")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            clap::Arg::new("challenge")
+                .long("challenge")
+                .help("challenge (nonce) provided by the verifier")
+                .required(true)
+                .env("CHALLENGE"),
+        )
+        .arg(
+            clap::Arg::new("did")
+                .short('d')
+                .long("did")
+                .help("holder DID")
+                .required(true)
+                .value_parser(DidParser)
+                .env("DID"),
+        )
+        .arg(
+            clap::Arg::new("seed")
+                .long("seed")
+                .help("Seed of the DID authentication key")
+                .required(true)
+                .env("SEED"),
+        )
+        .arg(
+            clap::Arg::new("key-type")
+                .long("key-type")
+                .help("Type of signing key")
+                .env("TYPE")
+                .value_parser(["sr25519", "ed25519", "ecdsa"])
+                .default_value("sr25519"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let challenge: &String = matches.get_one("challenge").expect("need challenge");
+    let seed: &String = matches.get_one("seed").expect("need seed");
+    let key_type: &String = matches.get_one("key-type").expect("need key type");
+
+    let credentials = match matches.get_many::<String>("credential") {
+        Some(credentials) => credentials
+            .map(|c| serde_json::from_str::<Credential>(c))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => {
+            let input = unwrap_or_stdin(None)?;
+            match serde_json::from_str::<Vec<Credential>>(&input) {
+                Ok(credentials) => credentials,
+                Err(_) => vec![serde_json::from_str::<Credential>(&input)?],
+            }
+        }
+    };
+
+    let cli = connect(matches).await?;
+    let did = &resolve_did(matches, &cli, matches.get_one::<DidArg>("did").unwrap()).await?;
+    let details = storage_at(matches, &cli)
+        .await?
+        .fetch(&kiltapi::kilt::storage().did().did(did))
+        .await?
+        .ok_or("DID not found")?;
+    let (auth_key_type, auth_key) = details
+        .public_keys
+        .0
+        .iter()
+        .find(|(id, _)| *id == details.authentication_key)
+        .map(|(_, key)| public_key(&key.key))
+        .ok_or("authentication key not found")?;
+
+    let (signer_key_type, signer_key) = public_key(&DidPublicKey::PublicVerificationKey(
+        did::verification_key(key_type, seed)?,
+    ));
+    if auth_key_type != signer_key_type || auth_key != signer_key {
+        return Err("seed does not match the DID authentication key".into());
+    }
+
+    let holder = did_uri(did);
+    let mut presentation = Presentation::new(
+        &holder,
+        credentials,
+        challenge,
+        &format!("{holder}#{:?}", details.authentication_key),
+        &Utc::now().to_rfc3339(),
+    );
+    let signature = did::sign(key_type, seed, &presentation.signing_payload()?)?;
+    presentation.set_signature(did::signature_bytes(&signature).1);
+
+    print_output(
+        matches,
+        serde_json::to_string_pretty(&presentation)?,
+        serde_json::to_value(&presentation)?,
+    );
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use base58::FromBase58;
 use clap::ArgAction;
-use kiltapi::{connect, credential::Credential, unwrap_or_stdin};
+use kiltapi::{
+    connect, credential::Credential, print_output, storage_at, unwrap_or_stdin, Storage,
+};
 use subxt::ext::sp_core::H256;
 
 pub fn command() -> clap::Command {
@@ -24,7 +26,7 @@ pub fn command() -> clap::Command {
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
-    let mut trusted_issuers = matches
+    let trusted_issuers = matches
         .get_many::<String>("trusted-issuer")
         .unwrap()
         .map(|e| e.to_owned());
@@ -37,15 +39,34 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
 
     let cred: Credential = serde_json::from_str(&credential)?;
 
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+    verify_credential(&storage, &cred, &trusted_issuers.collect::<Vec<_>>()).await?;
+
+    print_output(
+        matches,
+        "ok",
+        serde_json::json!({ "id": cred.id, "verified": true }),
+    );
+
+    Ok(())
+}
+
+// Check the proof, the issuer and the on-chain attestation of a credential.
+pub async fn verify_credential(
+    storage: &Storage,
+    cred: &Credential,
+    trusted_issuers: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
     cred.verify()?;
 
-    let issuer = if let Some(issuer) = cred.issuer {
+    let issuer = if let Some(issuer) = &cred.issuer {
         issuer
     } else {
         return Err("no issuer specified in credential".into());
     };
 
-    if !trusted_issuers.any(|x| x == issuer) {
+    if !trusted_issuers.contains(issuer) {
         return Err("issuer is not trusted".into());
     }
 
@@ -60,8 +81,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         .attestation()
         .attestations(H256(root_hash));
 
-    let cli = connect(matches).await?;
-    let attestation = cli.storage().at_latest().await?.fetch(&addr).await?;
+    let attestation = storage.fetch(&addr).await?;
     if let Some(attestation) = attestation {
         if attestation.revoked {
             return Err("attestation is revoked".into());
@@ -70,7 +90,5 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         return Err("attestation not found".into());
     }
 
-    println!("ok");
-
     Ok(())
 }
@@ -0,0 +1,121 @@
+use clap::ArgAction;
+use kiltapi::{
+    connect,
+    credential::Presentation,
+    did::{did_uri, public_key, verify_signature},
+    print_output, storage_at, unwrap_or_stdin,
+};
+use subxt::utils::AccountId32;
+
+use super::verify::verify_credential;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("verify-presentation")
+        .about("Verify a presentation and the credentials it contains")
+        .arg(
+            clap::Arg::new("presentation")
+                .short('p')
+                .long("presentation")
+                .help("presentation to verify")
+                .env("PRESENTATION"),
+        )
+        .arg(
+            clap::Arg::new("challenge")
+                .long("challenge")
+                .help("challenge (nonce) the presentation must be signed for")
+                .required(true)
+                .env("CHALLENGE"),
+        )
+        .arg(
+            clap::Arg::new("trusted-issuer")
+                .long("trusted-issuer")
+                .help("trusted issuer")
+                .default_value("")
+                .action(ArgAction::Append)
+                .env("ISSUER"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let challenge: &String = matches.get_one("challenge").expect("need challenge");
+    let trusted_issuers = matches
+        .get_many::<String>("trusted-issuer")
+        .unwrap()
+        .map(|e| e.to_owned())
+        .collect::<Vec<_>>();
+
+    let presentation = unwrap_or_stdin(
+        matches
+            .get_one::<String>("presentation")
+            .map(|e| e.to_owned()),
+    )?;
+    let presentation: Presentation = serde_json::from_str(&presentation)?;
+
+    if presentation.proof.challenge != *challenge {
+        return Err("challenge does not match".into());
+    }
+
+    let did: AccountId32 = presentation
+        .holder
+        .trim_start_matches("did:kilt:")
+        .parse()
+        .map_err(|_| "invalid holder DID")?;
+    let holder = did_uri(&did);
+
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+    let details = storage
+        .fetch(&kiltapi::kilt::storage().did().did(&did))
+        .await?
+        .ok_or("holder DID not found")?;
+
+    if presentation.proof.verification_method
+        != format!("{holder}#{:?}", details.authentication_key)
+    {
+        return Err("presentation is not signed with the holder's authentication key".into());
+    }
+    let (key_type, key) = details
+        .public_keys
+        .0
+        .iter()
+        .find(|(id, _)| *id == details.authentication_key)
+        .map(|(_, key)| public_key(&key.key))
+        .ok_or("authentication key not found")?;
+
+    if !verify_signature(
+        key_type,
+        &key,
+        &presentation.signing_payload()?,
+        &presentation.signature()?,
+    ) {
+        return Err("invalid presentation signature".into());
+    }
+
+    for cred in &presentation.verifiable_credential {
+        let subject = cred
+            .credential_subject
+            .get("@id")
+            .and_then(|id| id.as_str())
+            .unwrap_or_default();
+        if subject != presentation.holder && subject != holder {
+            return Err(format!("holder is not the subject of credential {}", cred.id).into());
+        }
+        verify_credential(&storage, cred, &trusted_issuers).await?;
+    }
+
+    print_output(
+        matches,
+        "ok",
+        serde_json::json!({
+            "holder": holder,
+            "credentials": presentation
+                .verifiable_credential
+                .iter()
+                .map(|cred| cred.id.clone())
+                .collect::<Vec<_>>(),
+            "verified": true,
+        }),
+    );
+
+    Ok(())
+}
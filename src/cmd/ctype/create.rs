@@ -1,6 +1,4 @@
-use kiltapi::unwrap_or_stdin;
-
-use crate::ctype::CType;
+use kiltapi::{ctype::CType, print_output, unwrap_or_stdin};
 
 pub fn command() -> clap::Command {
     clap::Command::new("create")
@@ -21,6 +19,14 @@ pub fn command() -> clap::Command {
                 .required(true)
                 .env("PROPERTIES"),
         )
+        .arg(
+            clap::Arg::new("metaschema")
+                .long("metaschema")
+                .help("CType metaschema version")
+                .value_parser(["draft-01", "v1"])
+                .default_value("draft-01")
+                .env("METASCHEMA"),
+        )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
@@ -34,9 +40,16 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     )?;
     let props: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&props_str)?;
 
-    let ctype = CType::new(title.to_owned(), props);
+    let ctype = match matches.get_one::<String>("metaschema").map(|e| e.as_str()) {
+        Some("v1") => CType::new_v1(title.to_owned(), props),
+        _ => CType::new(title.to_owned(), props),
+    };
 
-    println!("{}", serde_json::to_string_pretty(&ctype)?);
+    print_output(
+        matches,
+        serde_json::to_string_pretty(&ctype)?,
+        serde_json::to_value(&ctype)?,
+    );
 
     Ok(())
 }
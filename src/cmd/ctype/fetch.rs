@@ -0,0 +1,28 @@
+use kiltapi::{connect, ctype, output_json, storage_at, HashParser};
+use subxt::ext::sp_core::H256;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("fetch")
+        .about("Fetch a CType definition from the chain")
+        .arg(
+            clap::Arg::new("hash")
+                .long("hash")
+                .required(true)
+                .value_parser(HashParser)
+                .help("ctype hash to lookup")
+                .env("HASH"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let hash = matches.get_one::<H256>("hash").unwrap();
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+    let ctype = ctype::fetch_json(&cli, &storage, *hash).await?;
+    if output_json(matches) {
+        println!("{ctype}");
+    } else {
+        println!("{}", serde_json::to_string_pretty(&ctype)?);
+    }
+    Ok(())
+}
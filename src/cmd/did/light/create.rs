@@ -0,0 +1,75 @@
+use clap::ArgAction;
+use kiltapi::{
+    did::{light::LightDid, Service},
+    print_output, KeyAgreementKeyParser, ServiceParser,
+};
+use subxt::ext::sp_core::{ed25519, sr25519, Pair};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("create")
+        .about("Create a light DID from a seed")
+        .arg(
+            clap::Arg::new("seed")
+                .short('s')
+                .long("seed")
+                .help("Seed of the authentication key")
+                .required(true)
+                .env("SEED"),
+        )
+        .arg(
+            clap::Arg::new("key-type")
+                .short('k')
+                .long("key-type")
+                .help("Type of the authentication key")
+                .default_value("sr25519")
+                .value_parser(["sr25519", "ed25519"])
+                .env("KEY_TYPE"),
+        )
+        .arg(
+            clap::Arg::new("key-agreement-key")
+                .long("key-agreement-key")
+                .help("Key agreement key (x25519:0x...)")
+                .value_parser(KeyAgreementKeyParser),
+        )
+        .arg(
+            clap::Arg::new("service")
+                .long("service")
+                .help("Service endpoint (id,type,url); can be repeated")
+                .action(ArgAction::Append)
+                .value_parser(ServiceParser),
+        )
+}
+
+pub fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let seed: &String = matches.get_one("seed").expect("need seed");
+    let key_type: &String = matches.get_one("key-type").expect("need key type");
+
+    let auth_key = match key_type.as_str() {
+        "sr25519" => {
+            sr25519::Pair::from_string_with_seed(seed, None)
+                .map_err(|_| "bad seed")?
+                .0
+                .public()
+                .0
+        }
+        "ed25519" => {
+            ed25519::Pair::from_string_with_seed(seed, None)
+                .map_err(|_| "bad seed")?
+                .0
+                .public()
+                .0
+        }
+        _ => unreachable!(),
+    };
+
+    let mut did = LightDid::new(key_type, auth_key)?;
+    did.key_agreement_key = matches.get_one::<[u8; 32]>("key-agreement-key").copied();
+    did.services = matches
+        .get_many::<Service>("service")
+        .map(|services| services.cloned().collect())
+        .unwrap_or_default();
+
+    let uri = did.uri();
+    print_output(matches, &uri, serde_json::json!({ "did": uri }));
+    Ok(())
+}
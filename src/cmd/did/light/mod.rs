@@ -0,0 +1,19 @@
+mod create;
+mod resolve;
+mod upgrade;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("light")
+        .about("Off-chain light DIDs")
+        .subcommand_required(true)
+        .subcommands([create::command(), resolve::command(), upgrade::command()])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("create", matches)) => create::run(matches),
+        Some(("resolve", matches)) => resolve::run(matches),
+        Some(("upgrade", matches)) => upgrade::run(matches).await,
+        _ => Err("no valid subcommand".into()),
+    }
+}
@@ -0,0 +1,24 @@
+use kiltapi::{did::light::LightDid, output_json};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("resolve")
+        .about("Resolve a light DID into a W3C DID document")
+        .arg(
+            clap::Arg::new("did")
+                .help("Light DID to resolve")
+                .required(true)
+                .env("DID"),
+        )
+}
+
+pub fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let did: &String = matches.get_one("did").expect("need did");
+    let document = LightDid::parse(did)?.document();
+
+    if output_json(matches) {
+        println!("{}", serde_json::to_string(&document)?);
+    } else {
+        println!("{}", serde_json::to_string_pretty(&document)?);
+    }
+    Ok(())
+}
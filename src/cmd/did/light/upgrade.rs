@@ -0,0 +1,65 @@
+use codec::Encode;
+use kiltapi::{
+    connect,
+    did::{self, light::LightDid, public_key},
+    kilt::{self, runtime_types::did::did_details::DidPublicKey},
+    print_call, AccountIdParser,
+};
+use subxt::tx::TxPayload;
+use subxt::utils::AccountId32;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("upgrade")
+        .about("Build a did create transaction that moves a light DID on chain")
+        .arg(
+            clap::Arg::new("did")
+                .help("Light DID to upgrade")
+                .required(true)
+                .env("DID"),
+        )
+        .arg(
+            clap::Arg::new("submitter")
+                .long("submitter")
+                .required(true)
+                .help("Submitter account of this extrinsic")
+                .value_parser(AccountIdParser),
+        )
+        .arg(
+            clap::Arg::new("seed")
+                .short('s')
+                .long("seed")
+                .help("Seed of the light DID authentication key")
+                .required(true)
+                .env("SEED"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let did: &String = matches.get_one("did").expect("need did");
+    let did = LightDid::parse(did)?;
+    let submitter = matches
+        .get_one::<AccountId32>("submitter")
+        .expect("need submitter")
+        .to_owned();
+    let seed: &String = matches.get_one("seed").expect("need seed");
+
+    let details = did.creation_details(submitter);
+    let payload = details.encode();
+
+    let key = public_key(&DidPublicKey::PublicVerificationKey(did::verification_key(
+        &did.auth_key_type,
+        seed,
+    )?));
+    if key.1 != did.auth_key {
+        return Err("seed does not match the light DID authentication key".into());
+    }
+    let signature = did::sign(&did.auth_key_type, seed, &payload)?;
+
+    let tx = kilt::tx().did().create(details, signature);
+
+    let cli = connect(matches).await?;
+    let payload = tx.encode_call_data(&cli.metadata())?;
+
+    print_call(matches, &payload);
+    Ok(())
+}
@@ -0,0 +1,17 @@
+mod light;
+mod resolve;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("did")
+        .about("DID helpers")
+        .subcommand_required(true)
+        .subcommands([resolve::command(), light::command()])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("resolve", matches)) => resolve::run(matches).await,
+        Some(("light", matches)) => light::run(matches).await,
+        _ => Err("no valid subcommand".into()),
+    }
+}
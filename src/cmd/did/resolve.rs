@@ -0,0 +1,27 @@
+use kiltapi::{connect, did, output_json, resolve_did, storage_at, DidArg, DidParser};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("resolve")
+        .about("Resolve a DID into a W3C DID document")
+        .arg(
+            clap::Arg::new("did")
+                .help("DID to resolve")
+                .required(true)
+                .value_parser(DidParser)
+                .env("DID"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let cli = connect(matches).await?;
+    let did = &resolve_did(matches, &cli, matches.get_one::<DidArg>("did").unwrap()).await?;
+    let storage = storage_at(matches, &cli).await?;
+    let result = did::resolve(&storage, did).await?;
+
+    if output_json(matches) {
+        println!("{}", serde_json::to_string(&result)?);
+    } else {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+mod request;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("linking")
+        .about("Account linking commands")
+        .subcommand_required(true)
+        .subcommands([request::command()])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("request", matches)) => request::run(matches).await,
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,65 @@
+use kiltapi::{
+    connect, did::did_uri, linking, print_output, resolve_did, ss58, DidArg, DidParser,
+    LinkableAccount, LinkableAccountParser,
+};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("request")
+        .about("Print the payload an account has to sign to be linked to a DID")
+        .arg(
+            clap::Arg::new("account")
+                .short('a')
+                .long("account")
+                .help("Account to link (ss58 or 0x ethereum address)")
+                .required(true)
+                .value_parser(LinkableAccountParser)
+                .env("ACCOUNT"),
+        )
+        .arg(
+            clap::Arg::new("did")
+                .short('d')
+                .long("did")
+                .help("DID the account gets linked to")
+                .required(true)
+                .value_parser(DidParser)
+                .env("DID"),
+        )
+        .arg(
+            clap::Arg::new("expiration")
+                .long("expiration")
+                .help("Block number at which the signature expires")
+                .required(true)
+                .value_parser(clap::value_parser!(u64))
+                .env("EXPIRATION"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let account = matches.get_one::<LinkableAccount>("account").unwrap();
+    // only web3 names need a connection to the chain
+    let did = &match matches.get_one::<DidArg>("did").unwrap() {
+        DidArg::Account(did) => did.clone(),
+        name => resolve_did(matches, &connect(matches).await?, name).await?,
+    };
+    let expiration = *matches.get_one::<u64>("expiration").unwrap();
+
+    let payload = linking::payload(account, did, expiration);
+    let message = String::from_utf8_lossy(&payload);
+    let (account, signature_type) = match account {
+        LinkableAccount::Ethereum(address) => (format!("0x{}", hex::encode(address)), "ethereum"),
+        LinkableAccount::Substrate(account) => (ss58(account), "sr25519|ed25519|ecdsa"),
+    };
+    print_output(
+        matches,
+        &message,
+        serde_json::json!({
+            "account": account,
+            "did": did_uri(did),
+            "expiration": expiration,
+            "signatureType": signature_type,
+            "payload": message,
+            "payloadHex": format!("0x{}", hex::encode(&payload)),
+        }),
+    );
+    Ok(())
+}
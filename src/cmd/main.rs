@@ -1,14 +1,18 @@
 mod credential;
 mod ctype;
+mod did;
+mod linking;
 mod storage;
 mod tx;
 mod util;
 mod version;
+mod w3n;
 
 use std::io::Write;
 
 use clap_complete::{generate, Generator, Shell};
 use kiltapi::kilt::{self};
+use kiltapi::BlockRefParser;
 
 fn command() -> clap::Command {
     clap::Command::new("kiltctl")
@@ -22,6 +26,23 @@ fn command() -> clap::Command {
                 .default_value("spiritnet")
                 .env("KILT_ENDPOINT"),
         )
+        .arg(
+            clap::Arg::new("output")
+                .short('o')
+                .long("output")
+                .global(true)
+                .help("Output format")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .env("KILT_OUTPUT"),
+        )
+        .arg(
+            clap::Arg::new("at")
+                .long("at")
+                .global(true)
+                .value_parser(BlockRefParser)
+                .help("Block number or hash to read the chain state at (transactions use the latest block)"),
+        )
         .subcommand_required(true)
         .subcommands([
             tx::command(),
@@ -29,6 +50,9 @@ fn command() -> clap::Command {
             storage::command(),
             credential::command(),
             ctype::command(),
+            did::command(),
+            w3n::command(),
+            linking::command(),
         ])
         .subcommand(
             clap::Command::new("completions")
@@ -62,7 +86,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(("storage", matches)) => storage::run(matches).await,
         Some(("credential", matches)) => credential::run(matches).await,
         Some(("ctype", matches)) => ctype::run(matches).await,
-        Some(("version", _)) => version::run(),
+        Some(("did", matches)) => did::run(matches).await,
+        Some(("w3n", matches)) => w3n::run(matches).await,
+        Some(("linking", matches)) => linking::run(matches).await,
+        Some(("version", matches)) => version::run(matches),
         Some(("completions", matches)) => {
             let shell = matches.get_one::<Shell>("shell").unwrap().to_owned();
             print_completions(shell, &mut std::io::stdout());
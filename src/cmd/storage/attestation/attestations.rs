@@ -0,0 +1,82 @@
+use kiltapi::{
+    connect,
+    did::did_uri,
+    kilt::{
+        self,
+        runtime_types::{
+            attestation::attestations::AttestationDetails,
+            runtime_common::authorization::AuthorizationId,
+        },
+    },
+    output_json, ss58, storage_at, HashParser,
+};
+use subxt::ext::sp_core::H256;
+
+use crate::storage::{is_listing, list_entries, with_listing, LISTING_ARGS};
+
+pub fn command() -> clap::Command {
+    with_listing(
+        clap::Command::new("attestations")
+            .about("Lookup the attestation of a claim hash")
+            .arg(
+                clap::Arg::new("claim-hash")
+                    .long("claim-hash")
+                    .help("claim hash (root hash of the credential) to lookup")
+                    .required_unless_present_any(LISTING_ARGS)
+                    .value_parser(HashParser)
+                    .env("CLAIM_HASH"),
+            ),
+    )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if is_listing(matches) {
+        // Blake2_128Concat(claim hash)
+        return list_entries(
+            matches,
+            "Attestation",
+            "Attestations",
+            |key, attestation: AttestationDetails| {
+                Ok(attestation_json(
+                    &H256::from_slice(&key[16..]),
+                    &attestation,
+                ))
+            },
+        )
+        .await;
+    }
+
+    let claim_hash = matches.get_one::<H256>("claim-hash").unwrap();
+    let addr = kilt::storage().attestation().attestations(claim_hash);
+
+    let cli = connect(matches).await?;
+    let attestation = storage_at(matches, &cli)
+        .await?
+        .fetch(&addr)
+        .await?
+        .ok_or("attestation not found")?;
+
+    let json = attestation_json(claim_hash, &attestation);
+    if output_json(matches) {
+        println!("{json}");
+    } else {
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    }
+    Ok(())
+}
+
+fn attestation_json(claim_hash: &H256, attestation: &AttestationDetails) -> serde_json::Value {
+    serde_json::json!({
+        "claimHash": format!("{claim_hash:?}"),
+        "ctypeHash": format!("{:?}", attestation.ctype_hash),
+        "attester": did_uri(&attestation.attester),
+        "authorizationId": attestation.authorization_id.as_ref().map(|id| match id {
+            AuthorizationId::Delegation(id) => format!("{id:?}"),
+        }),
+        "revoked": attestation.revoked,
+        "deposit": {
+            "owner": ss58(&attestation.deposit.owner),
+            "amount": attestation.deposit.amount.to_string(),
+        },
+    })
+}
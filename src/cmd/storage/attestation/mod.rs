@@ -0,0 +1,15 @@
+mod attestations;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("attestation")
+        .about("Attestation related storage entries")
+        .subcommand_required(true)
+        .subcommands([attestations::command()])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("attestations", matches)) => attestations::run(matches).await,
+        _ => Ok(()),
+    }
+}
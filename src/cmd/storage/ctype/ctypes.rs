@@ -1,37 +1,71 @@
 use kiltapi::{
     connect,
-    kilt::{self},
-    unwrap_or_stdin,
+    did::did_uri,
+    kilt::{self, runtime_types::ctype::ctype_entry::CtypeEntry},
+    print_output, storage_at, unwrap_or_stdin,
 };
-use subxt::ext::sp_core::{crypto::Ss58Codec, H256};
+use subxt::{
+    ext::sp_core::{crypto::Ss58Codec, H256},
+    utils::AccountId32,
+};
+
+use crate::storage::{is_listing, list_entries, with_listing};
 
 pub fn command() -> clap::Command {
-    clap::Command::new("ctypes")
-        .about("Access the CTypes list")
-        .arg(
-            clap::Arg::new("hash")
-                .long("hash")
-                .help("ctype hash to lookup")
-                .env("HASH"),
-        )
+    with_listing(
+        clap::Command::new("ctypes")
+            .about("Access the CTypes list")
+            .arg(
+                clap::Arg::new("hash")
+                    .long("hash")
+                    .help("ctype hash to lookup")
+                    .env("HASH"),
+            ),
+    )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if is_listing(matches) {
+        // Blake2_128Concat(ctype hash)
+        return list_entries(
+            matches,
+            "Ctype",
+            "Ctypes",
+            |key, entry: CtypeEntry<AccountId32, u64>| {
+                Ok(serde_json::json!({
+                    "hash": format!("0x{}", hex::encode(&key[16..])),
+                    "creator": did_uri(&entry.creator),
+                    "createdAt": entry.created_at,
+                }))
+            },
+        )
+        .await;
+    }
+
     let ctype_hash_str = unwrap_or_stdin(matches.get_one::<String>("hash").map(|e| e.to_owned()))?;
     let ctype_hash = hex::decode(ctype_hash_str.trim_start_matches("0x").trim())
         .map_err(|_| "failed to parse ctype hash")?;
-    let addr = kilt::storage().ctype().ctypes(H256(
-        TryInto::<[u8; 32]>::try_into(ctype_hash).map_err(|_| "failed to parse ctype hash")?,
-    ));
+    let ctype_hash =
+        TryInto::<[u8; 32]>::try_into(ctype_hash).map_err(|_| "failed to parse ctype hash")?;
+    let addr = kilt::storage().ctype().ctypes(H256(ctype_hash));
 
     let cli = connect(matches).await?;
-    let ctype = cli.storage().at_latest().await?.fetch(&addr).await?;
+    let ctype = storage_at(matches, &cli).await?.fetch(&addr).await?;
     if let Some(holder) = ctype {
-        println!(
-            "ctype owned by: did:kilt:{}",
+        let creator = format!(
+            "did:kilt:{}",
             subxt::ext::sp_core::crypto::AccountId32::from(holder.creator.0)
                 .to_ss58check_with_version(38u16.into())
         );
+        print_output(
+            matches,
+            format!("ctype owned by: {creator}"),
+            serde_json::json!({
+                "hash": format!("0x{}", hex::encode(ctype_hash)),
+                "creator": creator,
+                "createdAt": holder.created_at,
+            }),
+        );
     } else {
         return Err("ctype not found".into());
     }
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use kiltapi::{
+    connect,
+    delegation::permission_names,
+    did::did_uri,
+    kilt::{self, runtime_types::delegation::delegation_hierarchy::DelegationNode},
+    output_json, storage_at, HashParser,
+};
+use subxt::ext::sp_core::H256;
+
+use super::node_json;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("hierarchy")
+        .about("Show a delegation hierarchy with all its nodes")
+        .arg(
+            clap::Arg::new("id")
+                .long("id")
+                .help("hierarchy (root node) ID")
+                .required(true)
+                .value_parser(HashParser)
+                .env("DELEGATION_ID"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let id = matches.get_one::<H256>("id").unwrap();
+
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+
+    let hierarchy = storage
+        .fetch(&kilt::storage().delegation().delegation_hierarchies(id))
+        .await?
+        .ok_or("delegation hierarchy not found")?;
+
+    let mut nodes = HashMap::new();
+    let mut pending = vec![*id];
+    while let Some(node_id) = pending.pop() {
+        let node = storage
+            .fetch(&kilt::storage().delegation().delegation_nodes(node_id))
+            .await?
+            .ok_or_else(|| format!("delegation node {node_id:?} not found"))?;
+        pending.extend(node.children.0.iter().copied());
+        nodes.insert(node_id, node);
+    }
+
+    if output_json(matches) {
+        println!(
+            "{}",
+            serde_json::json!({
+                "id": format!("{id:?}"),
+                "ctypeHash": format!("{:?}", hierarchy.ctype_hash),
+                "root": tree_json(id, &nodes),
+            })
+        );
+        return Ok(());
+    }
+
+    println!("CType: {:?}", hierarchy.ctype_hash);
+    print_tree(id, &nodes, 0);
+    Ok(())
+}
+
+fn tree_json(id: &H256, nodes: &HashMap<H256, DelegationNode>) -> serde_json::Value {
+    let node = &nodes[id];
+    let mut json = node_json(id, node);
+    json["children"] = node
+        .children
+        .0
+        .iter()
+        .map(|child| tree_json(child, nodes))
+        .collect::<Vec<_>>()
+        .into();
+    json
+}
+
+fn print_tree(id: &H256, nodes: &HashMap<H256, DelegationNode>, depth: usize) {
+    let node = &nodes[id];
+    println!(
+        "{}{id:?} {} [{}]{}",
+        "  ".repeat(depth),
+        did_uri(&node.details.owner),
+        permission_names(node.details.permissions.bits).join(","),
+        if node.details.revoked {
+            " (revoked)"
+        } else {
+            ""
+        }
+    );
+    for child in &node.children.0 {
+        print_tree(child, nodes, depth + 1);
+    }
+}
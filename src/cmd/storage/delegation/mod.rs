@@ -0,0 +1,39 @@
+use kiltapi::{
+    delegation::permission_names, did::did_uri,
+    kilt::runtime_types::delegation::delegation_hierarchy::DelegationNode, ss58,
+};
+use subxt::ext::sp_core::H256;
+
+mod hierarchy;
+mod node;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("delegation")
+        .about("Delegation related storage entries")
+        .subcommand_required(true)
+        .subcommands([node::command(), hierarchy::command()])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("node", matches)) => node::run(matches).await,
+        Some(("hierarchy", matches)) => hierarchy::run(matches).await,
+        _ => Ok(()),
+    }
+}
+
+fn node_json(id: &H256, node: &DelegationNode) -> serde_json::Value {
+    serde_json::json!({
+        "id": format!("{id:?}"),
+        "hierarchy": format!("{:?}", node.hierarchy_root_id),
+        "parent": node.parent.map(|parent| format!("{parent:?}")),
+        "children": node.children.0.iter().map(|child| format!("{child:?}")).collect::<Vec<_>>(),
+        "owner": did_uri(&node.details.owner),
+        "revoked": node.details.revoked,
+        "permissions": permission_names(node.details.permissions.bits),
+        "deposit": {
+            "owner": ss58(&node.deposit.owner),
+            "amount": node.deposit.amount.to_string(),
+        },
+    })
+}
@@ -0,0 +1,55 @@
+use kiltapi::{
+    connect,
+    kilt::{self, runtime_types::delegation::delegation_hierarchy::DelegationNode},
+    output_json, storage_at, HashParser,
+};
+use subxt::ext::sp_core::H256;
+
+use super::node_json;
+use crate::storage::{is_listing, list_entries, with_listing, LISTING_ARGS};
+
+pub fn command() -> clap::Command {
+    with_listing(
+        clap::Command::new("node")
+            .about("Lookup a delegation node")
+            .arg(
+                clap::Arg::new("id")
+                    .long("id")
+                    .help("delegation node ID to lookup")
+                    .required_unless_present_any(LISTING_ARGS)
+                    .value_parser(HashParser)
+                    .env("DELEGATION_ID"),
+            ),
+    )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if is_listing(matches) {
+        // Blake2_128Concat(node id)
+        return list_entries(
+            matches,
+            "Delegation",
+            "DelegationNodes",
+            |key, node: DelegationNode| Ok(node_json(&H256::from_slice(&key[16..]), &node)),
+        )
+        .await;
+    }
+
+    let id = matches.get_one::<H256>("id").unwrap();
+    let addr = kilt::storage().delegation().delegation_nodes(id);
+
+    let cli = connect(matches).await?;
+    let node = storage_at(matches, &cli)
+        .await?
+        .fetch(&addr)
+        .await?
+        .ok_or("delegation node not found")?;
+
+    let json = node_json(id, &node);
+    if output_json(matches) {
+        println!("{json}");
+    } else {
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    }
+    Ok(())
+}
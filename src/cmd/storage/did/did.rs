@@ -1,32 +1,89 @@
-use kiltapi::{connect, AccountIdParser};
+use codec::Decode;
+use kiltapi::{
+    connect,
+    did::{did_uri, public_key},
+    kilt::runtime_types::did::did_details::DidDetails,
+    output_json, resolve_did, ss58, storage_at, DidArg, DidParser,
+};
 use subxt::utils::AccountId32;
 
+use crate::storage::{is_listing, list_entries, with_listing, LISTING_ARGS};
+
 pub fn command() -> clap::Command {
-    clap::Command::new("did").about("Lookup a DID").arg(
-        clap::Arg::new("did")
-            .short('d')
-            .long("did")
-            .help("DID to query")
-            .required(true)
-            .value_parser(AccountIdParser)
-            .env("DID"),
+    with_listing(
+        clap::Command::new("did").about("Lookup a DID").arg(
+            clap::Arg::new("did")
+                .short('d')
+                .long("did")
+                .help("DID to query")
+                .required_unless_present_any(LISTING_ARGS)
+                .value_parser(DidParser)
+                .env("DID"),
+        ),
     )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
-    let did = matches.get_one::<AccountId32>("did").unwrap();
-
-    let addr = kiltapi::kilt::storage().did().did(did);
+    if is_listing(matches) {
+        // Blake2_128Concat(did)
+        return list_entries(matches, "Did", "Did", |key, details: DidDetails| {
+            let did = AccountId32::decode(&mut &key[16..])?;
+            Ok(did_details_json(&did, &details))
+        })
+        .await;
+    }
 
     let cli = connect(matches).await?;
-    let details = cli
-        .storage()
-        .at_latest()
+    let did = &resolve_did(matches, &cli, matches.get_one::<DidArg>("did").unwrap()).await?;
+
+    let addr = kiltapi::kilt::storage().did().did(did);
+    let details = storage_at(matches, &cli)
         .await?
         .fetch(&addr)
         .await?
-        .expect("not found");
-    println!("{details:#?}");
+        .ok_or("DID not found")?;
+
+    if output_json(matches) {
+        println!("{}", did_details_json(did, &details));
+    } else {
+        println!("{details:#?}");
+    }
 
     Ok(())
 }
+
+fn did_details_json(did: &AccountId32, details: &DidDetails) -> serde_json::Value {
+    let public_keys = details
+        .public_keys
+        .0
+        .iter()
+        .map(|(id, key)| {
+            let (key_type, public_key) = public_key(&key.key);
+            serde_json::json!({
+                "id": format!("{id:?}"),
+                "type": key_type,
+                "publicKey": format!("0x{}", hex::encode(public_key)),
+                "blockNumber": key.block_number,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::json!({
+        "did": did_uri(did),
+        "authenticationKey": format!("{:?}", details.authentication_key),
+        "attestationKey": details.attestation_key.map(|k| format!("{k:?}")),
+        "delegationKey": details.delegation_key.map(|k| format!("{k:?}")),
+        "keyAgreementKeys": details
+            .key_agreement_keys
+            .0
+            .iter()
+            .map(|k| format!("{k:?}"))
+            .collect::<Vec<_>>(),
+        "publicKeys": public_keys,
+        "lastTxCounter": details.last_tx_counter,
+        "deposit": {
+            "owner": ss58(&details.deposit.owner),
+            "amount": details.deposit.amount.to_string(),
+        },
+    })
+}
@@ -0,0 +1,60 @@
+use codec::{Decode, Encode};
+use kiltapi::{
+    blake2_128_concat, connect,
+    did::did_uri,
+    fetch_prefix,
+    kilt::{self, runtime_types::pallet_did_lookup::linkable_account::LinkableAccountId},
+    output_json, resolve_did, storage_at, DidArg, DidParser,
+};
+
+use super::{account_string, connection_json};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("accounts-of")
+        .about("List the accounts linked to a DID")
+        .arg(
+            clap::Arg::new("did")
+                .short('d')
+                .long("did")
+                .help("DID to lookup")
+                .required(true)
+                .value_parser(DidParser)
+                .env("DID"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let cli = connect(matches).await?;
+    let did = &resolve_did(matches, &cli, matches.get_one::<DidArg>("did").unwrap()).await?;
+    let storage = storage_at(matches, &cli).await?;
+
+    // Blake2_128Concat(did) ++ Blake2_128Concat(linkable account)
+    let accounts = fetch_prefix::<()>(
+        &storage,
+        "DidLookup",
+        "ConnectedAccounts",
+        &blake2_128_concat(&did.encode()),
+    )
+    .await?;
+
+    let mut connections = vec![];
+    for (key, _) in accounts {
+        let account = LinkableAccountId::decode(&mut &key[16..])?;
+        // the deposit is stored with the link from the account to the DID
+        let connection = storage
+            .fetch(&kilt::storage().did_lookup().connected_dids(&account))
+            .await?
+            .ok_or_else(|| format!("no connection record for {}", account_string(&account)))?;
+        connections.push(connection_json(&account, &connection));
+    }
+
+    if output_json(matches) {
+        println!(
+            "{}",
+            serde_json::json!({ "did": did_uri(did), "accounts": connections })
+        );
+    } else {
+        println!("{}", serde_json::to_string_pretty(&connections)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use codec::Decode;
+use kiltapi::{
+    connect,
+    kilt::{self, runtime_types::pallet_did_lookup::linkable_account::LinkableAccountId},
+    output_json, storage_at, LinkableAccount, LinkableAccountParser,
+};
+
+use super::{connection_json, Connection};
+use crate::storage::{is_listing, list_entries, with_listing, LISTING_ARGS};
+
+pub fn command() -> clap::Command {
+    with_listing(
+        clap::Command::new("did-of")
+            .about("Lookup the DID an account is linked to")
+            .arg(
+                clap::Arg::new("account")
+                    .short('a')
+                    .long("account")
+                    .help("account to lookup (ss58 or 0x ethereum address)")
+                    .required_unless_present_any(LISTING_ARGS)
+                    .value_parser(LinkableAccountParser)
+                    .env("ACCOUNT"),
+            ),
+    )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if is_listing(matches) {
+        // Blake2_128Concat(linkable account)
+        return list_entries(
+            matches,
+            "DidLookup",
+            "ConnectedDids",
+            |key, connection: Connection| {
+                let account = LinkableAccountId::decode(&mut &key[16..])?;
+                Ok(connection_json(&account, &connection))
+            },
+        )
+        .await;
+    }
+
+    let account: LinkableAccountId = matches
+        .get_one::<LinkableAccount>("account")
+        .unwrap()
+        .into();
+    let addr = kilt::storage().did_lookup().connected_dids(&account);
+
+    let cli = connect(matches).await?;
+    let connection = storage_at(matches, &cli)
+        .await?
+        .fetch(&addr)
+        .await?
+        .ok_or("account is not linked")?;
+
+    let json = connection_json(&account, &connection);
+    if output_json(matches) {
+        println!("{json}");
+    } else {
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    }
+    Ok(())
+}
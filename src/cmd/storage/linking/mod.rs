@@ -0,0 +1,46 @@
+use kiltapi::{
+    did::did_uri,
+    kilt::runtime_types::pallet_did_lookup::{
+        connection_record::ConnectionRecord, linkable_account::LinkableAccountId,
+    },
+    ss58,
+};
+use subxt::utils::AccountId32;
+
+mod accounts_of;
+mod did_of;
+
+type Connection = ConnectionRecord<AccountId32, AccountId32, u128>;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("linking")
+        .about("Account linking related storage entries")
+        .subcommand_required(true)
+        .subcommands([did_of::command(), accounts_of::command()])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("did-of", matches)) => did_of::run(matches).await,
+        Some(("accounts-of", matches)) => accounts_of::run(matches).await,
+        _ => Ok(()),
+    }
+}
+
+fn account_string(account: &LinkableAccountId) -> String {
+    match account {
+        LinkableAccountId::AccountId20(account) => format!("0x{}", hex::encode(account.0)),
+        LinkableAccountId::AccountId32(account) => ss58(account),
+    }
+}
+
+fn connection_json(account: &LinkableAccountId, connection: &Connection) -> serde_json::Value {
+    serde_json::json!({
+        "account": account_string(account),
+        "did": did_uri(&connection.did),
+        "deposit": {
+            "owner": ss58(&connection.deposit.owner),
+            "amount": connection.deposit.amount.to_string(),
+        },
+    })
+}
@@ -1,7 +1,14 @@
+use kiltapi::{connect, fetch_page, storage_at, storage_prefix};
+
+mod attestation;
 mod ctype;
+mod delegation;
 mod did;
+mod linking;
 mod public_credentials;
+mod staking;
 mod system;
+mod w3n;
 
 pub fn command() -> clap::Command {
     clap::Command::new("storage")
@@ -12,6 +19,11 @@ pub fn command() -> clap::Command {
             system::command(),
             ctype::command(),
             public_credentials::command(),
+            attestation::command(),
+            delegation::command(),
+            w3n::command(),
+            linking::command(),
+            staking::command(),
         ])
 }
 
@@ -21,6 +33,74 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         Some(("system", matches)) => system::run(matches).await,
         Some(("ctype", matches)) => ctype::run(matches).await,
         Some(("public-credentials", matches)) => public_credentials::run(matches).await,
+        Some(("attestation", matches)) => attestation::run(matches).await,
+        Some(("delegation", matches)) => delegation::run(matches).await,
+        Some(("w3n", matches)) => w3n::run(matches).await,
+        Some(("linking", matches)) => linking::run(matches).await,
+        Some(("staking", matches)) => staking::run(matches).await,
         _ => Ok(()),
     }
 }
+
+pub const LISTING_ARGS: [&str; 3] = ["all", "page-size", "start-key"];
+
+// Add the arguments to list the entries of a storage map instead of looking up a single key.
+pub fn with_listing(cmd: clap::Command) -> clap::Command {
+    cmd.arg(
+        clap::Arg::new("all")
+            .long("all")
+            .action(clap::ArgAction::SetTrue)
+            .help("list all entries (one JSON object per line)"),
+    )
+    .arg(
+        clap::Arg::new("page-size")
+            .long("page-size")
+            .value_parser(clap::value_parser!(u32).range(1..=1000))
+            .help("number of entries to fetch per request; without --all only one page is listed"),
+    )
+    .arg(
+        clap::Arg::new("start-key")
+            .long("start-key")
+            .help("storage key to continue a previous listing after"),
+    )
+}
+
+pub fn is_listing(matches: &clap::ArgMatches) -> bool {
+    matches.get_flag("all") || matches.contains_id("page-size") || matches.contains_id("start-key")
+}
+
+// Print the entries of a storage map as JSON lines. `render` gets the (hashed) key after the map
+// prefix; every line also contains the full storage key, which can be passed to `--start-key`.
+pub async fn list_entries<V: codec::Decode>(
+    matches: &clap::ArgMatches,
+    pallet: &str,
+    entry: &str,
+    render: impl Fn(&[u8], V) -> Result<serde_json::Value, Box<dyn std::error::Error>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let page_size = matches.get_one::<u32>("page-size").copied().unwrap_or(100);
+    let mut start_key = match matches.get_one::<String>("start-key") {
+        Some(key) => Some(hex::decode(key.trim_start_matches("0x").trim())?),
+        None => None,
+    };
+    let prefix = storage_prefix(pallet, entry);
+
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+    loop {
+        let page = fetch_page::<V>(&storage, &prefix, page_size, start_key.as_deref()).await?;
+        // a short page of keys means the end of the map
+        let done = page.len() < page_size as usize || !matches.get_flag("all");
+        for (key, value) in page {
+            if let Some(value) = value {
+                let mut line = render(&key[prefix.len()..], value)?;
+                line["key"] = format!("0x{}", hex::encode(&key)).into();
+                println!("{line}");
+            }
+            start_key = Some(key);
+        }
+        if done {
+            break;
+        }
+    }
+    Ok(())
+}
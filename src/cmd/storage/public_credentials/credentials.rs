@@ -1,110 +1,193 @@
-use codec::Decode;
+use codec::{Decode, Encode};
 use kilt_asset_dids::AssetDid;
 use kiltapi::{
-    connect, format_balance,
+    connect,
+    did::did_uri,
+    fetch_prefix, format_balance,
     kilt::{
         self,
         runtime_types::{
+            self,
+            kilt_asset_dids::{
+                asset::v1::{AssetId, EvmSmartContractNonFungibleReference},
+                chain::v1::ChainId,
+            },
             public_credentials::credentials::CredentialEntry,
-            runtime_common::authorization::AuthorizationId, self,
+            runtime_common::authorization::AuthorizationId,
         },
     },
-    unwrap_or_stdin,
+    output_json, ss58, storage_at, twox_64_concat, HashParser,
 };
-use subxt::ext::sp_core::{
-    crypto::{AccountId32, Ss58Codec},
-    H256,
+use subxt::{
+    ext::sp_core::{H256, U256},
+    utils::AccountId32,
 };
 
+use crate::storage::{is_listing, list_entries, with_listing, LISTING_ARGS};
+
 type Entry = CredentialEntry<H256, AccountId32, u64, AccountId32, u128, AuthorizationId<H256>>;
+type Subject = runtime_types::runtime_common::assets::AssetDid;
 
 pub fn command() -> clap::Command {
-    clap::Command::new("credentials")
-        .about("Access the credentials for a asset-did list")
-        .arg(
-            clap::Arg::new("asset-did")
-                .long("asset-did")
-                .help("asset-did to lookup")
-                .env("ASSET_DID"),
-        )
-        .arg(
-            clap::Arg::new("credential-id")
-                .long("credential-id")
-                .help("credential-id to lookup")
-                .env("CREDENTIAL_ID"),
-        )
+    with_listing(
+        clap::Command::new("credentials")
+            .about("Access the credentials for a asset-did list")
+            .arg(
+                clap::Arg::new("asset-did")
+                    .long("asset-did")
+                    .help("asset-did to lookup; lists all its credentials without --credential-id")
+                    .required_unless_present_any(LISTING_ARGS.into_iter().chain(["credential-id"]))
+                    .env("ASSET_DID"),
+            )
+            .arg(
+                clap::Arg::new("credential-id")
+                    .long("credential-id")
+                    .help("credential-id to lookup")
+                    .value_parser(HashParser)
+                    .env("CREDENTIAL_ID"),
+            ),
+    )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if is_listing(matches) {
+        // Twox64Concat(subject) ++ Blake2_128Concat(credential id)
+        return list_entries(
+            matches,
+            "PublicCredentials",
+            "Credentials",
+            |key, entry: Entry| {
+                let mut rest = &key[8..];
+                let subject = Subject::decode(&mut rest)?;
+                let id = H256::from_slice(&rest[16..]);
+                Ok(credential_json(&asset_did_uri(&subject.0), &id, &entry))
+            },
+        )
+        .await;
+    }
 
-    return Err("not implemented yet due to missing code generation from subxt. Check if https://github.com/paritytech/subxt/pull/1079 is merged and then fix.".into());
-
-    // let asset_did_str =
-    //     unwrap_or_stdin(matches.get_one::<String>("asset-did").map(|e| e.to_owned()))?;
-
-    // let did =
-    //     AssetDid::from_utf8_encoded(asset_did_str).map_err(|_| "failed to parse asset did")?;
-
-    // let credential_id = matches
-    //     .get_one::<String>("credential-id")
-    //     .map(|e| e.to_owned());
-
+    let credential_id = matches.get_one::<H256>("credential-id");
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
 
-    // let cli = connect(matches).await?;
+    let subject = match matches.get_one::<String>("asset-did") {
+        Some(asset_did) => {
+            let did = AssetDid::from_utf8_encoded(asset_did.trim())
+                .map_err(|_| "failed to parse asset did")?;
+            Subject::decode(&mut &did.encode()[..])?
+        }
+        None => {
+            // reverse lookup of the subject of a credential
+            let id = credential_id.expect("need asset did or credential id");
+            storage
+                .fetch(&kilt::storage().public_credentials().credential_subjects(id))
+                .await?
+                .ok_or("credential not found")?
+        }
+    };
+    let subject_uri = asset_did_uri(&subject.0);
 
-    // if let Some(id) = credential_id {
-    //     let id = H256(
-    //         hex::decode(id.trim_start_matches("0x").trim())
-    //             .map_err(|_| "failed to parse credential id")?
-    //             .try_into()
-    //             .map_err(|_| "failed to parse credential id")?,
-    //     );
+    let credentials = match credential_id {
+        Some(id) => {
+            let addr = kilt::storage()
+                .public_credentials()
+                .credentials(&subject, id);
+            vec![(
+                *id,
+                storage.fetch(&addr).await?.ok_or("credential not found")?,
+            )]
+        }
+        None => fetch_prefix::<Entry>(
+            &storage,
+            "PublicCredentials",
+            "Credentials",
+            &twox_64_concat(&subject.encode()),
+        )
+        .await?
+        .into_iter()
+        .map(|(key, entry)| (H256::from_slice(&key[16..]), entry))
+        .collect(),
+    };
 
-    //     let key = subxt::storage::dynamic("PublicCredentials", "Credentials", vec![id]);
-    //     let attestation_data = cli
-    //         .storage()
-    //         .at_latest()
-    //         .await?
-    //         .fetch(&key)
-    //         .await?
-    //         .ok_or("no attestation found")?;
-    //     let attestation = Entry::decode(&mut &attestation_data.into_encoded()[..])?;
-    //     print_credential_entry(attestation);
-    // } else {
-    //     let addr = kilt::storage().public_credentials().credentials_root();
-    //     let query_key = addr.to_root_bytes();  
-    //     let keys = cli
-    //         .storage()
-    //         .at_latest()
-    //         .await?
-    //         .fetch_keys(&query_key, 10, None)
-    //         .await?;
-    //     for key in keys {
-    //         println!(
-    //             "Credential ID: 0x{}",
-    //             hex::encode(&key.0[key.0.len() - 32..])
-    //         );
-    //         if let Some(storage_data) = cli.storage().at_latest().await?.fetch_raw(&key.0).await? {
-    //             let value = Entry::decode(&mut &storage_data[..])?;
-    //             print_credential_entry(value);
-    //         }
-    //     }
-    // }
-    // Ok(())
+    if output_json(matches) {
+        let credentials = credentials
+            .iter()
+            .map(|(id, entry)| credential_json(&subject_uri, id, entry))
+            .collect::<Vec<_>>();
+        match credential_id {
+            Some(_) => println!("{}", credentials[0]),
+            None => println!("{}", serde_json::Value::from(credentials)),
+        }
+    } else {
+        println!("Subject: {subject_uri}");
+        for (id, entry) in credentials {
+            println!();
+            println!("Credential ID: {id:?}");
+            print_credential_entry(entry);
+        }
+    }
+    Ok(())
 }
 
-fn print_credential_entry(
-    entry: CredentialEntry<H256, AccountId32, u64, AccountId32, u128, AuthorizationId<H256>>,
-) {
+fn print_credential_entry(entry: Entry) {
     println!("CType Hash: {:?}", entry.ctype_hash);
-    println!(
-        "Attester: {}",
-        entry.attester.to_ss58check_with_version(38u16.into())
-    );
+    println!("Attester: {}", did_uri(&entry.attester));
     println!("Revoked: {}", entry.revoked);
     println!("Deposit: {}", format_balance(entry.deposit.amount));
-    println!(
-        "Deposit Owner: {}",
-        entry.deposit.owner.to_ss58check_with_version(38u16.into())
-    );
+    println!("Deposit Owner: {}", ss58(&entry.deposit.owner));
+}
+
+fn credential_json(subject: &str, id: &H256, entry: &Entry) -> serde_json::Value {
+    serde_json::json!({
+        "subject": subject,
+        "credentialId": format!("{id:?}"),
+        "ctypeHash": format!("{:?}", entry.ctype_hash),
+        "attester": did_uri(&entry.attester),
+        "revoked": entry.revoked,
+        "blockNumber": entry.block_number,
+        "authorizationId": entry.authorization_id.as_ref().map(|id| match id {
+            AuthorizationId::Delegation(id) => format!("{id:?}"),
+        }),
+        "deposit": {
+            "owner": ss58(&entry.deposit.owner),
+            "amount": entry.deposit.amount.to_string(),
+        },
+    })
+}
+
+// Render an on-chain asset DID in its `did:asset:<chain>.<asset>` form.
+fn asset_did_uri(did: &runtime_types::kilt_asset_dids::v1::AssetDid) -> String {
+    let utf8 = |bytes: &[u8]| String::from_utf8_lossy(bytes).to_string();
+    let chain = match &did.chain_id {
+        ChainId::Eip155(reference) => format!("eip155:{}", reference.0),
+        ChainId::Bip122(reference) => format!("bip122:{}", hex::encode(reference.0)),
+        ChainId::Dotsama(reference) => format!("polkadot:{}", hex::encode(reference.0)),
+        ChainId::Solana(reference) => format!("solana:{}", utf8(&reference.0 .0)),
+        ChainId::Generic(id) => {
+            format!("{}:{}", utf8(&id.namespace.0 .0), utf8(&id.reference.0 .0))
+        }
+    };
+    let contract =
+        |namespace: &str, reference: &EvmSmartContractNonFungibleReference| match &reference.1 {
+            Some(id) => format!(
+                "{namespace}:0x{}:{}",
+                hex::encode(reference.0 .0),
+                utf8(&id.0 .0)
+            ),
+            None => format!("{namespace}:0x{}", hex::encode(reference.0 .0)),
+        };
+    let asset = match &did.asset_id {
+        AssetId::Slip44(reference) => format!("slip44:{}", U256(reference.0 .0)),
+        AssetId::Erc20(reference) => format!("erc20:0x{}", hex::encode(reference.0)),
+        AssetId::Erc721(reference) => contract("erc721", reference),
+        AssetId::Erc1155(reference) => contract("erc1155", reference),
+        AssetId::Generic(id) => {
+            let asset = format!("{}:{}", utf8(&id.namespace.0 .0), utf8(&id.reference.0 .0));
+            match &id.id {
+                Some(id) => format!("{asset}:{}", utf8(&id.0 .0)),
+                None => asset,
+            }
+        }
+    };
+    format!("did:asset:{chain}.{asset}")
 }
@@ -0,0 +1,65 @@
+use kiltapi::{
+    connect, fetch_prefix, format_balance,
+    kilt::runtime_types::parachain_staking::types::{Candidate, CandidateStatus},
+    output_json, ss58, storage_at,
+};
+use subxt::utils::AccountId32;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("candidates").about("List all collator candidates")
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+
+    let mut candidates = fetch_prefix::<Candidate<AccountId32, u128>>(
+        &storage,
+        "ParachainStaking",
+        "CandidatePool",
+        &[],
+    )
+    .await?
+    .into_iter()
+    .map(|(_, candidate)| candidate)
+    .collect::<Vec<_>>();
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.total));
+
+    if output_json(matches) {
+        let candidates = candidates
+            .iter()
+            .map(|candidate| {
+                let (status, leaving) = match candidate.status {
+                    CandidateStatus::Active => ("active", None),
+                    CandidateStatus::Leaving(round) => ("leaving", Some(round)),
+                };
+                serde_json::json!({
+                    "account": ss58(&candidate.id),
+                    "stake": candidate.stake.to_string(),
+                    "total": candidate.total.to_string(),
+                    "delegators": candidate.delegators.0 .0.len(),
+                    "status": status,
+                    "leavingRound": leaving,
+                })
+            })
+            .collect::<Vec<_>>();
+        println!("{}", serde_json::Value::from(candidates));
+        return Ok(());
+    }
+
+    for candidate in candidates {
+        let status = match candidate.status {
+            CandidateStatus::Active => "active".to_string(),
+            CandidateStatus::Leaving(round) => format!("leaving in round {round}"),
+        };
+        println!(
+            "{}: total {}, self stake {}, {} delegators, {}",
+            ss58(&candidate.id),
+            format_balance(candidate.total),
+            format_balance(candidate.stake),
+            candidate.delegators.0 .0.len(),
+            status
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use kiltapi::{connect, format_balance, kilt, output_json, ss58, storage_at, AccountIdParser};
+use subxt::utils::AccountId32;
+
+use super::block_number;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("delegator")
+        .about("Show the delegation and pending unstaking of an account")
+        .arg(
+            clap::Arg::new("account")
+                .short('a')
+                .long("account")
+                .help("delegator account")
+                .required(true)
+                .value_parser(AccountIdParser)
+                .env("ACCOUNT"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let account = matches.get_one::<AccountId32>("account").unwrap();
+
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+
+    let delegation = storage
+        .fetch(&kilt::storage().parachain_staking().delegator_state(account))
+        .await?;
+    let unstaking = storage
+        .fetch_or_default(&kilt::storage().parachain_staking().unstaking(account))
+        .await?;
+    let block = block_number(&storage).await?;
+
+    if output_json(matches) {
+        let unstaking = unstaking
+            .0
+            .iter()
+            .map(|(unlock, amount)| {
+                serde_json::json!({
+                    "block": unlock,
+                    "amount": amount.to_string(),
+                    "unlockable": *unlock <= block,
+                })
+            })
+            .collect::<Vec<_>>();
+        println!(
+            "{}",
+            serde_json::json!({
+                "account": ss58(account),
+                "collator": delegation.as_ref().map(|d| ss58(&d.owner)),
+                "amount": delegation.as_ref().map(|d| d.amount.to_string()),
+                "unstaking": unstaking,
+                "block": block,
+            })
+        );
+        return Ok(());
+    }
+
+    match delegation {
+        Some(delegation) => {
+            println!("Collator: {}", ss58(&delegation.owner));
+            println!("Amount: {}", format_balance(delegation.amount));
+        }
+        None => println!("No delegation"),
+    }
+    for (unlock, amount) in unstaking.0 {
+        if unlock <= block {
+            println!("Unstaking: {} unlockable now", format_balance(amount));
+        } else {
+            println!(
+                "Unstaking: {} unlocks at block {unlock} (in {} blocks)",
+                format_balance(amount),
+                unlock - block
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use kiltapi::{kilt, Storage};
+
+mod candidates;
+mod delegator;
+mod rewards;
+mod round;
+mod top_candidates;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("staking")
+        .about("Parachain staking related storage entries")
+        .subcommand_required(true)
+        .subcommands([
+            candidates::command(),
+            top_candidates::command(),
+            delegator::command(),
+            rewards::command(),
+            round::command(),
+        ])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("candidates", matches)) => candidates::run(matches).await,
+        Some(("top-candidates", matches)) => top_candidates::run(matches).await,
+        Some(("delegator", matches)) => delegator::run(matches).await,
+        Some(("rewards", matches)) => rewards::run(matches).await,
+        Some(("round", matches)) => round::run(matches).await,
+        _ => Ok(()),
+    }
+}
+
+// The number of the block the storage is read at.
+async fn block_number(storage: &Storage) -> Result<u64, Box<dyn std::error::Error>> {
+    Ok(storage
+        .fetch_or_default(&kilt::storage().system().number())
+        .await?)
+}
@@ -0,0 +1,167 @@
+use kiltapi::{
+    connect, format_balance,
+    kilt::{
+        self,
+        runtime_types::parachain_staking::{inflation::StakingInfo, types::CandidateStatus},
+    },
+    output_json, ss58, storage_at, AccountIdParser,
+};
+use subxt::utils::AccountId32;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("rewards")
+        .about("Show the staking rewards of an account that can be claimed")
+        .arg(
+            clap::Arg::new("account")
+                .short('a')
+                .long("account")
+                .help("collator or delegator account")
+                .required(true)
+                .value_parser(AccountIdParser)
+                .env("ACCOUNT"),
+        )
+}
+
+const ONE: u128 = 1_000_000_000_000_000_000;
+
+// Perquintill::from_rational (rounding down) for `part <= total`, split in two steps to not
+// overflow.
+fn from_rational(part: u128, total: u128) -> u128 {
+    const HALF: u128 = 1_000_000_000;
+    let high = part * HALF / total;
+    let rem = part * HALF % total;
+    high * HALF + rem * HALF / total
+}
+
+// The per block reward rate (a Perquintill). If more than `max_rate` of the total issuance is
+// staked by collators (or delegators), the rate is reduced by `max_rate / staking_rate`.
+fn reward_rate(info: &StakingInfo, staked: u128, issuance: u128) -> u64 {
+    let per_block = info.reward_rate.per_block.0 as u128;
+    let max_rate = info.max_rate.0 as u128;
+    let staking_rate = match issuance {
+        0 => ONE,
+        _ => from_rational(staked.min(issuance), issuance),
+    };
+    if staking_rate <= max_rate {
+        return per_block as u64;
+    }
+    (from_rational(max_rate, staking_rate) * per_block / ONE) as u64
+}
+
+// Rewards for `blocks` blocks at the given per block rate (a Perquintill) for `stake`.
+fn block_rewards(stake: u128, rate: u64, blocks: u64) -> u128 {
+    // Perquintill * Balance rounds to the nearest value, preferring down
+    let rem = stake % ONE * rate as u128;
+    let per_block = stake / ONE * rate as u128 + rem / ONE + (rem % ONE > ONE / 2) as u128;
+    per_block * blocks as u128
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let account = matches.get_one::<AccountId32>("account").unwrap();
+
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+    let staking = kilt::storage().parachain_staking();
+
+    let rewards = storage.fetch_or_default(&staking.rewards(account)).await?;
+    let rewarded = storage
+        .fetch_or_default(&staking.blocks_rewarded(account))
+        .await?;
+    let inflation = storage
+        .fetch_or_default(&staking.inflation_config())
+        .await?;
+    let total_stake = storage
+        .fetch_or_default(&staking.total_collator_stake())
+        .await?;
+    let issuance = storage
+        .fetch_or_default(&kilt::storage().balances().total_issuance())
+        .await?;
+
+    // Blocks authored since the last reward increment are not yet part of `Rewards`; compute them
+    // the same way the runtime does when rewards are incremented or claimed.
+    let (role, authored, unclaimed) =
+        if let Some(delegation) = storage.fetch(&staking.delegator_state(account)).await? {
+            let authored = storage
+                .fetch_or_default(&staking.blocks_authored(&delegation.owner))
+                .await?;
+            let unclaimed = block_rewards(
+                delegation.amount,
+                reward_rate(&inflation.delegator, total_stake.delegators, issuance),
+                authored.saturating_sub(rewarded),
+            );
+            ("delegator", authored, unclaimed)
+        } else if let Some(candidate) = storage
+            .fetch(&staking.candidate_pool(account))
+            .await?
+            .filter(|candidate| matches!(candidate.status, CandidateStatus::Active))
+        {
+            let authored = storage
+                .fetch_or_default(&staking.blocks_authored(account))
+                .await?;
+            let unclaimed = block_rewards(
+                candidate.stake,
+                reward_rate(&inflation.collator, total_stake.collators, issuance),
+                authored.saturating_sub(rewarded),
+            );
+            ("collator", authored, unclaimed)
+        } else {
+            ("none", 0, 0)
+        };
+
+    if output_json(matches) {
+        println!(
+            "{}",
+            serde_json::json!({
+                "account": ss58(account),
+                "role": role,
+                "rewards": rewards.to_string(),
+                "unclaimed": unclaimed.to_string(),
+                "total": (rewards + unclaimed).to_string(),
+                "blocksAuthored": authored,
+                "blocksRewarded": rewarded,
+            })
+        );
+        return Ok(());
+    }
+
+    println!("Role: {role}");
+    println!("Rewards: {}", format_balance(rewards));
+    println!(
+        "Not yet incremented: {} ({} blocks)",
+        format_balance(unclaimed),
+        authored.saturating_sub(rewarded)
+    );
+    println!("Total: {}", format_balance(rewards + unclaimed));
+    Ok(())
+}
+
+mod test {
+    #[test]
+    fn test_reward_rate() {
+        use super::*;
+        use kiltapi::kilt::runtime_types::{
+            parachain_staking::inflation::RewardRate, sp_arithmetic::per_things::Perquintill,
+        };
+
+        assert_eq!(from_rational(1, 3), 333_333_333_333_333_333);
+        assert_eq!(
+            from_rational(u64::MAX as u128 * 7, u64::MAX as u128 * 8),
+            ONE / 8 * 7
+        );
+
+        let info = StakingInfo {
+            max_rate: Perquintill(400_000_000_000_000_000),
+            reward_rate: RewardRate {
+                annual: Perquintill(0),
+                per_block: Perquintill(1_000_000_000),
+            },
+        };
+        // 40% staked: full rate, 50% staked: reduced by 0.4 / 0.5
+        assert_eq!(reward_rate(&info, 400, 1000), 1_000_000_000);
+        assert_eq!(reward_rate(&info, 500, 1000), 800_000_000);
+
+        assert_eq!(block_rewards(3 * ONE, 1_000_000_000, 2), 6_000_000_000);
+        assert_eq!(block_rewards(1_600_000_000, 1_000_000_000, 1), 2);
+        assert_eq!(block_rewards(2_500_000_000, 1_000_000_000, 1), 2);
+    }
+}
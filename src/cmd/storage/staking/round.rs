@@ -0,0 +1,38 @@
+use kiltapi::{connect, kilt, print_output, storage_at};
+
+use super::block_number;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("round").about("Show the current staking round")
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+
+    let round = storage
+        .fetch_or_default(&kilt::storage().parachain_staking().round())
+        .await?;
+    let block = block_number(&storage).await?;
+    let next = round.first + round.length;
+
+    print_output(
+        matches,
+        format!(
+            "Round: {}\nFirst block: {}\nLength: {}\nNext round: {} (in {} blocks)",
+            round.current,
+            round.first,
+            round.length,
+            next,
+            next.saturating_sub(block)
+        ),
+        serde_json::json!({
+            "round": round.current,
+            "first": round.first,
+            "length": round.length,
+            "nextRoundStart": next,
+            "block": block,
+        }),
+    );
+    Ok(())
+}
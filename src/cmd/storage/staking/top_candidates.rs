@@ -0,0 +1,55 @@
+use kiltapi::{connect, format_balance, kilt, output_json, ss58, storage_at};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("top-candidates")
+        .about("List the candidates with the highest stake and whether they are selected")
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let cli = connect(matches).await?;
+    let storage = storage_at(matches, &cli).await?;
+
+    let selected = storage
+        .fetch_or_default(
+            &kilt::storage()
+                .parachain_staking()
+                .max_selected_candidates(),
+        )
+        .await? as usize;
+    let top = storage
+        .fetch_or_default(&kilt::storage().parachain_staking().top_candidates())
+        .await?;
+
+    if output_json(matches) {
+        let candidates = top
+            .0
+             .0
+            .iter()
+            .enumerate()
+            .map(|(i, stake)| {
+                serde_json::json!({
+                    "account": ss58(&stake.owner),
+                    "total": stake.amount.to_string(),
+                    "selected": i < selected,
+                })
+            })
+            .collect::<Vec<_>>();
+        println!(
+            "{}",
+            serde_json::json!({ "maxSelected": selected, "candidates": candidates })
+        );
+        return Ok(());
+    }
+
+    println!("Max selected candidates: {selected}");
+    for (i, stake) in top.0 .0.iter().enumerate() {
+        println!(
+            "{:>3}. {} {}{}",
+            i + 1,
+            ss58(&stake.owner),
+            format_balance(stake.amount),
+            if i < selected { "" } else { " (not selected)" }
+        );
+    }
+    Ok(())
+}
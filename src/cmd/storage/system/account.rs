@@ -1,4 +1,5 @@
-use kiltapi::{connect, AccountIdParser};
+use kiltapi::{connect, output_json, storage_at, AccountIdParser};
+use subxt::ext::sp_core::crypto::Ss58Codec;
 use subxt::utils::AccountId32;
 
 pub fn command() -> clap::Command {
@@ -21,14 +22,31 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let addr = kiltapi::kilt::storage().system().account(account);
 
     let cli = connect(matches).await?;
-    let details = cli
-        .storage()
-        .at_latest()
+    let details = storage_at(matches, &cli)
         .await?
-        .fetch(&addr)
-        .await?
-        .expect("not found");
-    println!("{details:#?}");
+        .fetch_or_default(&addr)
+        .await?;
+    if output_json(matches) {
+        println!(
+            "{}",
+            serde_json::json!({
+                "account": subxt::ext::sp_core::crypto::AccountId32::from(account.0)
+                    .to_ss58check_with_version(38u16.into()),
+                "nonce": details.nonce,
+                "consumers": details.consumers,
+                "providers": details.providers,
+                "sufficients": details.sufficients,
+                "data": {
+                    "free": details.data.free.to_string(),
+                    "reserved": details.data.reserved.to_string(),
+                    "miscFrozen": details.data.misc_frozen.to_string(),
+                    "feeFrozen": details.data.fee_frozen.to_string(),
+                },
+            })
+        );
+    } else {
+        println!("{details:#?}");
+    }
 
     Ok(())
 }
@@ -0,0 +1,17 @@
+mod name;
+mod owner;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("w3n")
+        .about("Web3Name related storage entries")
+        .subcommand_required(true)
+        .subcommands([owner::command(), name::command()])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("owner", matches)) => owner::run(matches).await,
+        Some(("name", matches)) => name::run(matches).await,
+        _ => Ok(()),
+    }
+}
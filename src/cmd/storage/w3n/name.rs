@@ -0,0 +1,58 @@
+use codec::Decode;
+use kiltapi::{
+    connect,
+    did::did_uri,
+    kilt::{self, runtime_types::pallet_web3_names::web3_name::AsciiWeb3Name},
+    print_output, resolve_did, storage_at, DidArg, DidParser,
+};
+use subxt::utils::AccountId32;
+
+use crate::storage::{is_listing, list_entries, with_listing, LISTING_ARGS};
+
+pub fn command() -> clap::Command {
+    with_listing(
+        clap::Command::new("name")
+            .about("Lookup the web3 name of a DID")
+            .arg(
+                clap::Arg::new("did")
+                    .short('d')
+                    .long("did")
+                    .help("DID to lookup")
+                    .required_unless_present_any(LISTING_ARGS)
+                    .value_parser(DidParser)
+                    .env("DID"),
+            ),
+    )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if is_listing(matches) {
+        // Blake2_128Concat(did)
+        return list_entries(matches, "Web3Names", "Names", |key, name: AsciiWeb3Name| {
+            let did = AccountId32::decode(&mut &key[16..])?;
+            Ok(serde_json::json!({
+                "did": did_uri(&did),
+                "name": String::from_utf8_lossy(&name.0 .0),
+            }))
+        })
+        .await;
+    }
+
+    let cli = connect(matches).await?;
+    let did = &resolve_did(matches, &cli, matches.get_one::<DidArg>("did").unwrap()).await?;
+    let addr = kilt::storage().web3_names().names(did);
+
+    let name = storage_at(matches, &cli)
+        .await?
+        .fetch(&addr)
+        .await?
+        .ok_or("DID has no web3 name")?;
+
+    let name = String::from_utf8_lossy(&name.0 .0).to_string();
+    print_output(
+        matches,
+        &name,
+        serde_json::json!({ "did": did_uri(did), "name": name }),
+    );
+    Ok(())
+}
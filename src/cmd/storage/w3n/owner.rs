@@ -0,0 +1,78 @@
+use codec::Decode;
+use kiltapi::{
+    connect,
+    did::did_uri,
+    kilt::{
+        self,
+        runtime_types::{
+            kilt_support::deposit::Deposit,
+            pallet_web3_names::web3_name::{AsciiWeb3Name, Web3NameOwnership},
+            sp_core::bounded::bounded_vec::BoundedVec,
+        },
+    },
+    output_json, ss58, storage_at,
+};
+use subxt::utils::AccountId32;
+
+use crate::storage::{is_listing, list_entries, with_listing, LISTING_ARGS};
+
+type Ownership = Web3NameOwnership<AccountId32, Deposit<AccountId32, u128>, u64>;
+
+pub fn command() -> clap::Command {
+    with_listing(
+        clap::Command::new("owner")
+            .about("Lookup the owner of a web3 name")
+            .arg(
+                clap::Arg::new("name")
+                    .short('n')
+                    .long("name")
+                    .help("web3 name to lookup")
+                    .required_unless_present_any(LISTING_ARGS)
+                    .env("NAME"),
+            ),
+    )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if is_listing(matches) {
+        // Blake2_128Concat(name)
+        return list_entries(matches, "Web3Names", "Owner", |key, owner: Ownership| {
+            let name = AsciiWeb3Name::decode(&mut &key[16..])?;
+            Ok(owner_json(&String::from_utf8_lossy(&name.0 .0), &owner))
+        })
+        .await;
+    }
+
+    let name = matches.get_one::<String>("name").unwrap();
+    let name = name.trim_start_matches("w3n:");
+    let addr = kilt::storage()
+        .web3_names()
+        .owner(AsciiWeb3Name(BoundedVec(name.as_bytes().to_vec())));
+
+    let cli = connect(matches).await?;
+    let owner = storage_at(matches, &cli)
+        .await?
+        .fetch(&addr)
+        .await?
+        .ok_or("web3 name not found")?;
+
+    let json = owner_json(name, &owner);
+    if output_json(matches) {
+        println!("{json}");
+    } else {
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    }
+    Ok(())
+}
+
+fn owner_json(name: &str, owner: &Ownership) -> serde_json::Value {
+    serde_json::json!({
+        "name": name,
+        "owner": did_uri(&owner.owner),
+        "claimedAt": owner.claimed_at,
+        "deposit": {
+            "owner": ss58(&owner.deposit.owner),
+            "amount": owner.deposit.amount.to_string(),
+        },
+    })
+}
@@ -1,22 +1,26 @@
-use kiltapi::{connect, unwrap_or_stdin};
+use kiltapi::{connect, print_call, unwrap_or_stdin};
 use subxt::ext::sp_core::H256;
 use subxt::tx::TxPayload;
 
+use crate::tx::delegation::{authorization, with_authorization};
+
 pub fn command() -> clap::Command {
-    clap::Command::new("add")
-        .about("Add an attestation to the blockchain")
-        .arg(
-            clap::Arg::new("claim")
-                .long("claim")
-                .required(false) // will be read from stdin if not provided
-                .help("Claim hash"),
-        )
-        .arg(
-            clap::Arg::new("ctype")
-                .long("ctype")
-                .required(true)
-                .help("CType hash"),
-        )
+    with_authorization(
+        clap::Command::new("add")
+            .about("Add an attestation to the blockchain")
+            .arg(
+                clap::Arg::new("claim")
+                    .long("claim")
+                    .required(false) // will be read from stdin if not provided
+                    .help("Claim hash"),
+            )
+            .arg(
+                clap::Arg::new("ctype")
+                    .long("ctype")
+                    .required(true)
+                    .help("CType hash"),
+            ),
+    )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
@@ -28,12 +32,13 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let ctype_hash_bytes = hex::decode(ctype_hash_str.trim_start_matches("0x").trim())?;
     let ctype_hash = H256::from_slice(&ctype_hash_bytes);
 
-    let tx = crate::kilt::tx()
-        .attestation()
-        .add(claim_hash, ctype_hash, None);
-
     let cli = connect(matches).await?;
+    let tx =
+        crate::kilt::tx()
+            .attestation()
+            .add(claim_hash, ctype_hash, authorization(matches, &cli)?);
+
     let payload = tx.encode_call_data(&cli.metadata())?;
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
@@ -1,16 +1,20 @@
-use kiltapi::{connect, unwrap_or_stdin};
+use kiltapi::{connect, print_call, unwrap_or_stdin};
 use subxt::ext::sp_core::H256;
 use subxt::tx::TxPayload;
 
+use crate::tx::delegation::{authorization, with_authorization};
+
 pub fn command() -> clap::Command {
-    clap::Command::new("revoke")
-        .about("Revoke an attestation from the blockchain")
-        .arg(
-            clap::Arg::new("claim")
-                .long("claim")
-                .required(false) // will be read from stdin if not provided
-                .help("Claim hash"),
-        )
+    with_authorization(
+        clap::Command::new("revoke")
+            .about("Revoke an attestation from the blockchain")
+            .arg(
+                clap::Arg::new("claim")
+                    .long("claim")
+                    .required(false) // will be read from stdin if not provided
+                    .help("Claim hash"),
+            ),
+    )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
@@ -18,10 +22,12 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let claim_hash_bytes = hex::decode(claim_hash_str.trim_start_matches("0x").trim())?;
     let claim_hash = H256::from_slice(&claim_hash_bytes);
 
-    let tx = crate::kilt::tx().attestation().revoke(claim_hash, None);
-
     let cli = connect(matches).await?;
+    let tx = crate::kilt::tx()
+        .attestation()
+        .revoke(claim_hash, authorization(matches, &cli)?);
+
     let payload = tx.encode_call_data(&cli.metadata())?;
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
@@ -1,4 +1,4 @@
-use kiltapi::{connect, AccountIdParser, BalanceParser};
+use kiltapi::{connect, print_call, AccountIdParser, BalanceParser};
 use subxt::tx::TxPayload;
 use subxt::utils::AccountId32;
 
@@ -70,7 +70,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         }
     };
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
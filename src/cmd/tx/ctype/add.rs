@@ -1,8 +1,6 @@
-use kiltapi::{connect, unwrap_or_stdin};
+use kiltapi::{connect, ctype::CType, print_call, unwrap_or_stdin};
 use subxt::tx::TxPayload;
 
-use crate::ctype::CType;
-
 pub fn command() -> clap::Command {
     clap::Command::new("add")
         .about("Add a new CType to the chain")
@@ -25,6 +23,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
 
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
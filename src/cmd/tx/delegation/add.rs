@@ -0,0 +1,75 @@
+use kiltapi::{
+    connect, did,
+    kilt::{self, runtime_types::delegation::delegation_hierarchy::Permissions},
+    print_call, resolve_did, DidArg, DidParser,
+};
+use subxt::ext::sp_core::H256;
+use subxt::tx::TxPayload;
+
+use super::{creation_hash, permissions, with_node_args};
+
+pub fn command() -> clap::Command {
+    with_node_args(clap::Command::new("add").about("Add a delegation node to a hierarchy"))
+        .arg(
+            clap::Arg::new("delegate")
+                .long("delegate")
+                .help("DID of the delegate")
+                .required(true)
+                .value_parser(DidParser)
+                .env("DELEGATE"),
+        )
+        .arg(
+            clap::Arg::new("seed")
+                .long("seed")
+                .help("Seed of the delegate's authentication key")
+                .required_unless_present("signature")
+                .env("DELEGATE_SEED"),
+        )
+        .arg(
+            clap::Arg::new("key-type")
+                .long("key-type")
+                .help("Type of the delegate's authentication key")
+                .value_parser(["sr25519", "ed25519", "ecdsa"])
+                .default_value("sr25519")
+                .env("TYPE"),
+        )
+        .arg(
+            clap::Arg::new("signature")
+                .long("signature")
+                .help("Signature of the delegate (type:0x...), as printed by `sign`")
+                .conflicts_with("seed")
+                .env("DELEGATE_SIGNATURE"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let id = matches.get_one::<H256>("id").unwrap();
+    let parent = matches.get_one::<H256>("parent").unwrap();
+    let delegate = matches.get_one::<DidArg>("delegate").unwrap();
+
+    let cli = connect(matches).await?;
+    let delegate = resolve_did(matches, &cli, delegate).await?;
+    let signature = match matches.get_one::<String>("signature") {
+        Some(signature) => did::parse_signature(signature)?,
+        None => {
+            let seed = matches.get_one::<String>("seed").unwrap();
+            let key_type = matches.get_one::<String>("key-type").unwrap();
+            let hash = creation_hash(matches, &cli).await?;
+            did::sign(key_type, seed, hash.as_bytes())?
+        }
+    };
+
+    let tx = kilt::tx().delegation().add_delegation(
+        id.to_owned(),
+        parent.to_owned(),
+        delegate,
+        Permissions {
+            bits: permissions(matches),
+        },
+        signature,
+    );
+
+    let payload = tx.encode_call_data(&cli.metadata())?;
+    print_call(matches, &payload);
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use kiltapi::{connect, kilt, print_call, HashParser};
+use subxt::ext::sp_core::H256;
+use subxt::tx::TxPayload;
+
+use super::id_arg;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("create-hierarchy")
+        .about("Create a new delegation hierarchy")
+        .arg(id_arg(
+            "ID of the hierarchy root node (a unique 0x-prefixed 32 byte hash)",
+        ))
+        .arg(
+            clap::Arg::new("ctype")
+                .long("ctype")
+                .help("CType hash the hierarchy attests")
+                .required(true)
+                .value_parser(HashParser)
+                .env("CTYPE"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let id = matches.get_one::<H256>("id").unwrap();
+    let ctype = matches.get_one::<H256>("ctype").unwrap();
+
+    let tx = kilt::tx()
+        .delegation()
+        .create_hierarchy(id.to_owned(), ctype.to_owned());
+
+    let cli = connect(matches).await?;
+    let payload = tx.encode_call_data(&cli.metadata())?;
+    print_call(matches, &payload);
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use kiltapi::{
+    delegation::{self, Authorization, ATTEST, DELEGATE},
+    kilt::{self, KiltConfig},
+    storage_at, HashParser,
+};
+use subxt::ext::sp_core::H256;
+use subxt::OnlineClient;
+
+mod add;
+mod change_deposit_owner;
+mod create_hierarchy;
+mod reclaim_deposit;
+mod remove;
+mod revoke;
+mod sign;
+mod update_deposit;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("delegation")
+        .about("Delegation hierarchy transactions")
+        .subcommand_required(true)
+        .subcommands([
+            create_hierarchy::command(),
+            sign::command(),
+            add::command(),
+            revoke::command(),
+            remove::command(),
+            reclaim_deposit::command(),
+            change_deposit_owner::command(),
+            update_deposit::command(),
+        ])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("create-hierarchy", matches)) => create_hierarchy::run(matches).await,
+        Some(("sign", matches)) => sign::run(matches).await,
+        Some(("add", matches)) => add::run(matches).await,
+        Some(("revoke", matches)) => revoke::run(matches).await,
+        Some(("remove", matches)) => remove::run(matches).await,
+        Some(("reclaim-deposit", matches)) => reclaim_deposit::run(matches).await,
+        Some(("change-deposit-owner", matches)) => change_deposit_owner::run(matches).await,
+        Some(("update-deposit", matches)) => update_deposit::run(matches).await,
+        _ => unreachable!(),
+    }
+}
+
+fn id_arg(help: &'static str) -> clap::Arg {
+    clap::Arg::new("id")
+        .long("id")
+        .help(help)
+        .required(true)
+        .value_parser(HashParser)
+        .env("DELEGATION_ID")
+}
+
+// Arguments describing a new delegation node, shared by `add` and `sign`.
+fn with_node_args(cmd: clap::Command) -> clap::Command {
+    cmd.arg(id_arg(
+        "ID of the new delegation node (a unique 0x-prefixed 32 byte hash)",
+    ))
+    .arg(
+        clap::Arg::new("parent")
+            .long("parent")
+            .help("ID of the parent node (the hierarchy ID for top level delegations)")
+            .required(true)
+            .value_parser(HashParser),
+    )
+    .arg(
+        clap::Arg::new("hierarchy")
+            .long("hierarchy")
+            .help("ID of the hierarchy (looked up from the parent if omitted)")
+            .value_parser(HashParser),
+    )
+    .arg(
+        clap::Arg::new("permissions")
+            .long("permissions")
+            .help("Permissions of the delegate")
+            .value_parser(["attest", "delegate"])
+            .value_delimiter(',')
+            .action(clap::ArgAction::Append)
+            .default_value("attest"),
+    )
+}
+
+fn permissions(matches: &clap::ArgMatches) -> u32 {
+    matches
+        .get_many::<String>("permissions")
+        .unwrap()
+        .fold(0, |bits, permission| match permission.as_str() {
+            "attest" => bits | ATTEST,
+            _ => bits | DELEGATE,
+        })
+}
+
+// The hash the delegate has to sign for the node described by the arguments of `with_node_args`.
+async fn creation_hash(
+    matches: &clap::ArgMatches,
+    cli: &OnlineClient<KiltConfig>,
+) -> Result<H256, Box<dyn std::error::Error>> {
+    let id = matches.get_one::<H256>("id").unwrap();
+    let parent = matches.get_one::<H256>("parent").unwrap();
+    let hierarchy = match matches.get_one::<H256>("hierarchy") {
+        Some(hierarchy) => hierarchy.to_owned(),
+        None => {
+            storage_at(matches, cli)
+                .await?
+                .fetch(&kilt::storage().delegation().delegation_nodes(parent))
+                .await?
+                .ok_or("parent node not found")?
+                .hierarchy_root_id
+        }
+    };
+    Ok(delegation::creation_hash(
+        id,
+        &hierarchy,
+        parent,
+        permissions(matches),
+    ))
+}
+
+// Add the arguments to authorize an attestation or public credential operation with a delegation.
+pub fn with_authorization(cmd: clap::Command) -> clap::Command {
+    cmd.arg(
+        clap::Arg::new("authorization")
+            .long("authorization")
+            .help("Delegation node ID that authorizes the operation")
+            .value_parser(HashParser)
+            .env("AUTHORIZATION"),
+    )
+    .arg(
+        clap::Arg::new("max-checks")
+            .long("max-checks")
+            .help("Number of parent nodes to check for the authorization (defaults to the chain maximum)")
+            .value_parser(clap::value_parser!(u32))
+            .requires("authorization"),
+    )
+}
+
+pub fn authorization(
+    matches: &clap::ArgMatches,
+    cli: &OnlineClient<KiltConfig>,
+) -> Result<Option<Authorization>, Box<dyn std::error::Error>> {
+    let Some(node) = matches.get_one::<H256>("authorization") else {
+        return Ok(None);
+    };
+    let max_checks = match matches.get_one::<u32>("max-checks") {
+        Some(max_checks) => *max_checks,
+        None => cli
+            .constants()
+            .at(&kilt::constants().delegation().max_parent_checks())?,
+    };
+    Ok(Some(delegation::authorization(*node, max_checks)))
+}
@@ -0,0 +1,37 @@
+use kiltapi::{connect, kilt, print_call};
+use subxt::ext::sp_core::H256;
+use subxt::tx::TxPayload;
+
+use super::id_arg;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("reclaim-deposit")
+        .about("Reclaim the deposit of a delegation node, removing it and its children")
+        .arg(id_arg("ID of the node to remove"))
+        .arg(
+            clap::Arg::new("max-removals")
+                .long("max-removals")
+                .help("Maximum number of nodes to remove (defaults to the chain maximum)")
+                .value_parser(clap::value_parser!(u32)),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let id = matches.get_one::<H256>("id").unwrap();
+
+    let cli = connect(matches).await?;
+    let max_removals = match matches.get_one::<u32>("max-removals") {
+        Some(max) => *max,
+        None => cli
+            .constants()
+            .at(&kilt::constants().delegation().max_removals())?,
+    };
+
+    let tx = kilt::tx()
+        .delegation()
+        .reclaim_deposit(id.to_owned(), max_removals);
+
+    let payload = tx.encode_call_data(&cli.metadata())?;
+    print_call(matches, &payload);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use kiltapi::{connect, kilt, print_call};
+use subxt::ext::sp_core::H256;
+use subxt::tx::TxPayload;
+
+use super::id_arg;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("revoke")
+        .about("Revoke a delegation node and its children")
+        .arg(id_arg("ID of the node to revoke"))
+        .arg(
+            clap::Arg::new("max-parent-checks")
+                .long("max-parent-checks")
+                .help("Number of parents to check for a node owned by the sender (defaults to the chain maximum)")
+                .value_parser(clap::value_parser!(u32)),
+        )
+        .arg(
+            clap::Arg::new("max-revocations")
+                .long("max-revocations")
+                .help("Maximum number of nodes to revoke (defaults to the chain maximum)")
+                .value_parser(clap::value_parser!(u32)),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let id = matches.get_one::<H256>("id").unwrap();
+
+    let cli = connect(matches).await?;
+    let max_parent_checks = match matches.get_one::<u32>("max-parent-checks") {
+        Some(max) => *max,
+        None => cli
+            .constants()
+            .at(&kilt::constants().delegation().max_parent_checks())?,
+    };
+    let max_revocations = match matches.get_one::<u32>("max-revocations") {
+        Some(max) => *max,
+        None => cli
+            .constants()
+            .at(&kilt::constants().delegation().max_revocations())?,
+    };
+
+    let tx = kilt::tx().delegation().revoke_delegation(
+        id.to_owned(),
+        max_parent_checks,
+        max_revocations,
+    );
+
+    let payload = tx.encode_call_data(&cli.metadata())?;
+    print_call(matches, &payload);
+    Ok(())
+}
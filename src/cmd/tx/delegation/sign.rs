@@ -0,0 +1,44 @@
+use kiltapi::{connect, did, print_output};
+
+use super::{creation_hash, with_node_args};
+
+pub fn command() -> clap::Command {
+    with_node_args(
+        clap::Command::new("sign")
+            .about("Sign a delegation node as the delegate, to be passed to `add --signature`"),
+    )
+    .arg(
+        clap::Arg::new("seed")
+            .long("seed")
+            .help("Seed of the delegate's authentication key")
+            .required(true)
+            .env("SEED"),
+    )
+    .arg(
+        clap::Arg::new("key-type")
+            .long("key-type")
+            .help("Type of the delegate's authentication key")
+            .value_parser(["sr25519", "ed25519", "ecdsa"])
+            .default_value("sr25519")
+            .env("TYPE"),
+    )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let seed = matches.get_one::<String>("seed").unwrap();
+    let key_type = matches.get_one::<String>("key-type").unwrap();
+
+    let cli = connect(matches).await?;
+    let hash = creation_hash(matches, &cli).await?;
+    let signature = did::signature_string(&did::sign(key_type, seed, hash.as_bytes())?);
+
+    print_output(
+        matches,
+        &signature,
+        serde_json::json!({
+            "hash": format!("{hash:?}"),
+            "signature": signature,
+        }),
+    );
+    Ok(())
+}
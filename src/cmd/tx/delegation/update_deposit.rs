@@ -0,0 +1,22 @@
+use kiltapi::{connect, kilt, print_call};
+use subxt::ext::sp_core::H256;
+use subxt::tx::TxPayload;
+
+use super::id_arg;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("update-deposit")
+        .about("Update the deposit of a delegation node to the current deposit amount")
+        .arg(id_arg("ID of the delegation node"))
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let id = matches.get_one::<H256>("id").unwrap();
+
+    let tx = kilt::tx().delegation().update_deposit(id.to_owned());
+
+    let cli = connect(matches).await?;
+    let payload = tx.encode_call_data(&cli.metadata())?;
+    print_call(matches, &payload);
+    Ok(())
+}
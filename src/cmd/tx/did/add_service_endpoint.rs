@@ -7,6 +7,7 @@ use kiltapi::{
             did::service_endpoints::DidEndpoint, sp_core::bounded::bounded_vec::BoundedVec,
         },
     },
+    print_call,
 };
 use subxt::tx::TxPayload;
 
@@ -57,6 +58,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
@@ -6,7 +6,7 @@ use kiltapi::{
         runtime_types::did::did_details::{DidAuthorizedCallOperation, DidSignature},
         RuntimeCall,
     },
-    AccountIdParser,
+    print_call, resolve_did, AccountIdParser, DidArg, DidParser,
 };
 use subxt::ext::sp_core::{ecdsa, ed25519, sr25519, Pair};
 use subxt::tx::TxPayload;
@@ -21,7 +21,7 @@ pub fn command() -> clap::Command {
                 .long("did")
                 .help("DID to authorize")
                 .required(true)
-                .value_parser(AccountIdParser)
+                .value_parser(DidParser)
                 .env("DID"),
         )
         .arg(
@@ -57,10 +57,6 @@ pub fn command() -> clap::Command {
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
-    let did = matches
-        .get_one::<AccountId32>("did")
-        .expect("need did")
-        .to_owned();
     let submitter = matches
         .get_one::<AccountId32>("submitter")
         .expect("need submitter")
@@ -73,6 +69,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let tx_bytes = hex::decode(tx_hex.trim_start_matches("0x").trim())?;
 
     let cli = connect(matches).await?;
+    let did = resolve_did(matches, &cli, matches.get_one::<DidArg>("did").unwrap()).await?;
 
     let did_doc_addr = kilt::storage().did().did(&did);
     let tx_counter = cli
@@ -135,7 +132,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
 
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
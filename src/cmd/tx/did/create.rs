@@ -1,18 +1,19 @@
-use clap::Arg;
+use std::collections::BTreeSet;
+
+use clap::{Arg, ArgAction};
 use codec::Encode;
 use kiltapi::{
     connect,
+    did::{self, did_endpoint, parse_verification_key, Service},
     kilt::{
         self,
         runtime_types::{
-            self,
-            did::did_details::{DidCreationDetails, DidSignature, DidVerificationKey},
+            did::did_details::{DidCreationDetails, DidEncryptionKey},
             sp_core::bounded::bounded_btree_set::BoundedBTreeSet,
         },
     },
-    AccountIdParser,
+    print_call, AccountIdParser, KeyAgreementKeyParser, ServiceParser,
 };
-use subxt::ext::sp_core::{sr25519, Pair};
 use subxt::tx::TxPayload;
 use subxt::utils::AccountId32;
 
@@ -34,17 +35,40 @@ pub fn command() -> clap::Command {
                 .required(true)
                 .env("SEED"),
         )
+        .arg(
+            clap::Arg::new("key-type")
+                .short('k')
+                .long("key-type")
+                .help("Type of the authentication key")
+                .default_value("sr25519")
+                .value_parser(["sr25519", "ed25519", "ecdsa"])
+                .env("KEY_TYPE"),
+        )
         .arg(
             clap::Arg::new("attestation-key")
                 .long("attestation-key")
                 .required(false)
-                .help("Attestation public key"),
+                .help("Attestation public key (sr25519|ed25519|ecdsa:0x...; sr25519 if no type is given)"),
         )
         .arg(
             clap::Arg::new("delegation-key")
                 .long("delegation-key")
                 .required(false)
-                .help("Delegation public key"),
+                .help("Delegation public key (sr25519|ed25519|ecdsa:0x...; sr25519 if no type is given)"),
+        )
+        .arg(
+            clap::Arg::new("key-agreement-key")
+                .long("key-agreement-key")
+                .help("Key agreement key (x25519:0x...); can be repeated")
+                .action(ArgAction::Append)
+                .value_parser(KeyAgreementKeyParser),
+        )
+        .arg(
+            clap::Arg::new("service")
+                .long("service")
+                .help("Service endpoint (id,type,url); can be repeated")
+                .action(ArgAction::Append)
+                .value_parser(ServiceParser),
         )
 }
 
@@ -54,44 +78,49 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         .expect("need submitter")
         .to_owned();
     let seed: &String = matches.get_one("seed").expect("need seed");
-    let pair = sr25519::Pair::from_string_with_seed(seed, None)
-        .map_err(|_| "bad seed")?
-        .0;
-    let pub_key = pair.public();
-    let did: AccountId32 = pub_key.into();
+    let key_type: &String = matches.get_one("key-type").expect("need key type");
+
+    let did = did::did_account(&did::verification_key(key_type, seed)?);
+
+    let attestation_key = matches
+        .get_one::<String>("attestation-key")
+        .map(|key| parse_verification_key(key))
+        .transpose()?;
+
+    let delegation_key = matches
+        .get_one::<String>("delegation-key")
+        .map(|key| parse_verification_key(key))
+        .transpose()?;
 
-    let attestation_key = matches.get_one::<String>("attestation-key").map(|key| {
-        let bs: [u8; 32] = hex::decode(key.trim_start_matches("0x").trim())
-            .expect("failed to hex decode")
-            .try_into()
-            .expect("bad attestation key");
-        DidVerificationKey::Sr25519(runtime_types::sp_core::sr25519::Public(bs))
-    });
+    // the runtime stores the keys in a BTreeSet, so they have to be signed sorted and deduplicated
+    let key_agreement_keys = matches
+        .get_many::<[u8; 32]>("key-agreement-key")
+        .map(|keys| keys.copied().collect::<BTreeSet<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .map(DidEncryptionKey::X25519)
+        .collect();
 
-    let delegation_key = matches.get_one::<String>("delegation-key").map(|key| {
-        let bs: [u8; 32] = hex::decode(key.trim_start_matches("0x").trim())
-            .expect("failed to hex decode")
-            .try_into()
-            .expect("bad attestation key");
-        DidVerificationKey::Sr25519(runtime_types::sp_core::sr25519::Public(bs))
-    });
+    let services = matches
+        .get_many::<Service>("service")
+        .map(|services| services.map(did_endpoint).collect())
+        .unwrap_or_default();
 
     let details = DidCreationDetails {
         did,
         submitter,
         new_attestation_key: attestation_key,
         new_delegation_key: delegation_key,
-        new_key_agreement_keys: BoundedBTreeSet(vec![]),
-        new_service_details: vec![],
+        new_key_agreement_keys: BoundedBTreeSet(key_agreement_keys),
+        new_service_details: services,
     };
 
-    let sig = pair.sign(&details.encode());
-    let did_sig = DidSignature::Sr25519(runtime_types::sp_core::sr25519::Signature(sig.0));
+    let did_sig = did::sign(key_type, seed, &details.encode())?;
     let tx = kilt::tx().did().create(details, did_sig);
 
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
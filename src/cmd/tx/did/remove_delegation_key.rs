@@ -1,6 +1,7 @@
 use kiltapi::{
     connect,
     kilt::{self},
+    print_call,
 };
 use subxt::tx::TxPayload;
 
@@ -14,6 +15,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
@@ -1,6 +1,7 @@
 use kiltapi::{
     connect,
     kilt::{self},
+    print_call,
 };
 use subxt::ext::sp_core::H256;
 use subxt::tx::TxPayload;
@@ -31,6 +32,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
 
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
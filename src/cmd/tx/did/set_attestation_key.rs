@@ -7,6 +7,7 @@ use kiltapi::{
             sp_core::{ecdsa, ed25519, sr25519},
         },
     },
+    print_call,
 };
 use subxt::tx::TxPayload;
 pub fn command() -> clap::Command {
@@ -60,6 +61,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
 
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
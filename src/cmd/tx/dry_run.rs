@@ -0,0 +1,51 @@
+use codec::Decode;
+use kiltapi::{
+    connect, format_dispatch_error, kilt::runtime_types::sp_runtime::DispatchError, output_json,
+    unwrap_or_stdin,
+};
+use subxt::ext::sp_runtime::transaction_validity::TransactionValidityError;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("dry-run")
+        .about("Dry-run a signed transaction against the latest block")
+        .arg(
+            clap::Arg::new("tx")
+                .short('t')
+                .long("tx")
+                .help("Signed transaction to dry-run"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let tx = unwrap_or_stdin(matches.get_one("tx").map(|s: &String| s.to_owned()))?;
+    let tx = hex::decode(tx.trim_start_matches("0x").trim())?;
+
+    let cli = connect(matches).await?;
+    let result = cli.rpc().dry_run(&tx, None).await?;
+
+    // system_dryRun returns an ApplyExtrinsicResult
+    let result: Result<Result<(), DispatchError>, TransactionValidityError> =
+        Decode::decode(&mut &result.0[..])?;
+    let error = match result {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(format_dispatch_error(&err, &cli.metadata())),
+        Err(err) => Some(format!("Invalid transaction: {err:?}")),
+    };
+
+    if output_json(matches) {
+        println!(
+            "{}",
+            serde_json::json!({
+                "success": error.is_none(),
+                "error": error,
+            })
+        );
+    } else if error.is_none() {
+        println!("Success");
+    }
+
+    match error {
+        Some(error) => Err(format!("Dry run failed: {error}").into()),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,101 @@
+use codec::{Decode, Encode};
+use kiltapi::{
+    connect, format_balance,
+    kilt::runtime_types::{frame_support::dispatch::DispatchClass, sp_weights::weight_v2::Weight},
+    output_json, unwrap_or_stdin,
+};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("estimate")
+        .about("Estimate the fees of a signed transaction")
+        .arg(
+            clap::Arg::new("tx")
+                .short('t')
+                .long("tx")
+                .help("Signed transaction to estimate"),
+        )
+}
+
+// Mirrors pallet_transaction_payment::RuntimeDispatchInfo
+#[derive(Decode)]
+struct RuntimeDispatchInfo {
+    weight: Weight,
+    class: DispatchClass,
+    partial_fee: u128,
+}
+
+// Mirrors pallet_transaction_payment::FeeDetails
+#[derive(Decode)]
+struct FeeDetails {
+    inclusion_fee: Option<InclusionFee>,
+    tip: u128,
+}
+
+#[derive(Decode)]
+struct InclusionFee {
+    base_fee: u128,
+    len_fee: u128,
+    adjusted_weight_fee: u128,
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let tx = unwrap_or_stdin(matches.get_one("tx").map(|s: &String| s.to_owned()))?;
+    let tx = hex::decode(tx.trim_start_matches("0x").trim())?;
+
+    // Both runtime calls take the opaque extrinsic followed by its encoded length.
+    let mut params = tx.clone();
+    (tx.len() as u32).encode_to(&mut params);
+
+    let cli = connect(matches).await?;
+    let info: RuntimeDispatchInfo = cli
+        .rpc()
+        .state_call("TransactionPaymentApi_query_info", Some(&params), None)
+        .await?;
+    let details: FeeDetails = cli
+        .rpc()
+        .state_call(
+            "TransactionPaymentApi_query_fee_details",
+            Some(&params),
+            None,
+        )
+        .await?;
+    let inclusion_fee = details.inclusion_fee.unwrap_or(InclusionFee {
+        base_fee: 0,
+        len_fee: 0,
+        adjusted_weight_fee: 0,
+    });
+
+    if output_json(matches) {
+        println!(
+            "{}",
+            serde_json::json!({
+                "weight": {
+                    "refTime": info.weight.ref_time,
+                    "proofSize": info.weight.proof_size,
+                },
+                "class": format!("{:?}", info.class),
+                "partialFee": info.partial_fee.to_string(),
+                "baseFee": inclusion_fee.base_fee.to_string(),
+                "lenFee": inclusion_fee.len_fee.to_string(),
+                "adjustedWeightFee": inclusion_fee.adjusted_weight_fee.to_string(),
+                "tip": details.tip.to_string(),
+            })
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Weight: ref_time {}, proof_size {}",
+        info.weight.ref_time, info.weight.proof_size
+    );
+    println!("Class: {:?}", info.class);
+    println!("Partial fee: {}", format_balance(info.partial_fee));
+    println!("  Base fee: {}", format_balance(inclusion_fee.base_fee));
+    println!("  Length fee: {}", format_balance(inclusion_fee.len_fee));
+    println!(
+        "  Adjusted weight fee: {}",
+        format_balance(inclusion_fee.adjusted_weight_fee)
+    );
+    println!("Tip: {}", format_balance(details.tip));
+    Ok(())
+}
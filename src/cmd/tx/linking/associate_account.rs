@@ -1,7 +1,20 @@
-use codec::Encode;
-use kiltapi::kilt::runtime_types::pallet_did_lookup::associate_account_request::AssociateAccountRequest;
-use kiltapi::{connect, kilt, AccountIdParser};
-use subxt::ext::sp_core::{ecdsa, ed25519, sr25519, Pair};
+use kiltapi::{
+    connect,
+    kilt::{
+        self,
+        runtime_types::{
+            pallet_did_lookup::{
+                account::{AccountId20, EthereumSignature},
+                associate_account_request::AssociateAccountRequest,
+            },
+            sp_core as types,
+            sp_runtime::MultiSignature,
+        },
+    },
+    linking::{self, EthereumKey, DEFAULT_ETHEREUM_PATH},
+    print_call, resolve_did, DidArg, DidParser, LinkableAccount, LinkableAccountParser,
+};
+use subxt::ext::sp_core::{ecdsa, ed25519, hashing::blake2_256, sr25519, Pair};
 use subxt::tx::TxPayload;
 use subxt::utils::AccountId32;
 
@@ -12,46 +25,74 @@ pub fn command() -> clap::Command {
             clap::Arg::new("account")
                 .short('a')
                 .long("account")
-                .help("Account to link")
-                .value_parser(AccountIdParser)
+                .help("Account to link (derived from the seed if omitted)")
+                .value_parser(LinkableAccountParser)
                 .env("ACCOUNT"),
         )
+        .arg(
+            clap::Arg::new("account-type")
+                .long("account-type")
+                .help("Type of the account to link")
+                .value_parser(["substrate", "ethereum"])
+                .default_value("substrate")
+                .env("ACCOUNT_TYPE"),
+        )
+        .arg(
+            clap::Arg::new("did")
+                .short('d')
+                .long("did")
+                .help("DID the account gets linked to")
+                .required(true)
+                .value_parser(DidParser)
+                .env("DID"),
+        )
         .arg(
             clap::Arg::new("expiration")
-                .short('e')
                 .long("expiration")
                 .help("Block number at which the signatures expires")
+                .value_parser(clap::value_parser!(u64))
                 .env("EXPIRATION"),
         )
         .arg(
             clap::Arg::new("seed")
                 .short('s')
                 .long("seed")
-                .help("Seed to use for signing")
+                .help("Seed to use for signing (private key or mnemonic for ethereum accounts)")
+                .required_unless_present("signature")
                 .env("SEED"),
         )
         .arg(
-            clap::Arg::new("signature-algorithm")
-                .long("signature-algorithm")
-                .help("Signature algorithm to use for signing")
-                .value_parser(["sr25519", "ed25519", "ecdsa"])
+            clap::Arg::new("derivation-path")
+                .long("derivation-path")
+                .help("Derivation path for ethereum mnemonics")
+                .default_value(DEFAULT_ETHEREUM_PATH)
+                .env("DERIVATION_PATH"),
+        )
+        .arg(
+            clap::Arg::new("signature")
+                .long("signature")
+                .help("Externally produced signature (0x...) over the linking challenge")
+                .conflicts_with("seed")
+                .requires("expiration")
+                .env("SIGNATURE"),
+        )
+        .arg(
+            clap::Arg::new("signature-type")
+                .long("signature-type")
+                .alias("signature-algorithm")
+                .help("Type of the signature")
+                .value_parser(["sr25519", "ed25519", "ecdsa", "ethereum"])
                 .default_value("sr25519")
-                .env("SIGNATURE_ALGORITHM"),
+                .env("SIGNATURE_TYPE"),
         )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
-    let account = matches
-        .get_one::<AccountId32>("account")
-        .expect("need account");
-    let seed = matches.get_one::<String>("seed").expect("need seed");
-    let signature_algorithm = matches
-        .get_one::<String>("signature-algorithm")
-        .expect("need signature algorithm");
+    let cli = connect(matches).await?;
+    let did = &resolve_did(matches, &cli, matches.get_one::<DidArg>("did").unwrap()).await?;
     let expiration = match matches.get_one::<u64>("expiration") {
         Some(expiration) => expiration.to_owned(),
         None => {
-            let cli = connect(matches).await?;
             let block_number = cli
                 .rpc()
                 .block(None)
@@ -65,54 +106,117 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         }
     };
 
-    let tx = match signature_algorithm.as_str() {
+    let challenge = linking::challenge(did, expiration);
+    let ethereum = matches.get_one::<String>("account-type").unwrap() == "ethereum"
+        || matches.get_one::<String>("signature-type").unwrap() == "ethereum";
+    let req = if ethereum {
+        ethereum_request(matches, challenge.as_bytes())?
+    } else {
+        substrate_request(matches, &linking::wrap_bytes(challenge.as_bytes()))?
+    };
+    let tx = kilt::tx().did_lookup().associate_account(req, expiration);
+
+    let payload = tx.encode_call_data(&cli.metadata())?;
+    print_call(matches, &payload);
+    Ok(())
+}
+
+fn signature_bytes<const N: usize>(signature: &str) -> Result<[u8; N], Box<dyn std::error::Error>> {
+    hex::decode(signature.trim_start_matches("0x").trim())?
+        .try_into()
+        .map_err(|_| "signature malformed".into())
+}
+
+fn substrate_request(
+    matches: &clap::ArgMatches,
+    message: &[u8],
+) -> Result<AssociateAccountRequest, Box<dyn std::error::Error>> {
+    let account = match matches.get_one::<LinkableAccount>("account") {
+        Some(LinkableAccount::Substrate(account)) => Some(account.clone()),
+        Some(LinkableAccount::Ethereum(_)) => {
+            return Err("ethereum addresses need --account-type ethereum".into())
+        }
+        None => None,
+    };
+    let algorithm = matches.get_one::<String>("signature-type").unwrap();
+
+    if let Some(signature) = matches.get_one::<String>("signature") {
+        let account = account.ok_or("need account when passing a signature")?;
+        let signature = match algorithm.as_str() {
+            "sr25519" => {
+                MultiSignature::Sr25519(types::sr25519::Signature(signature_bytes(signature)?))
+            }
+            "ed25519" => {
+                MultiSignature::Ed25519(types::ed25519::Signature(signature_bytes(signature)?))
+            }
+            "ecdsa" => MultiSignature::Ecdsa(types::ecdsa::Signature(signature_bytes(signature)?)),
+            _ => unreachable!(),
+        };
+        return Ok(AssociateAccountRequest::Polkadot(account, signature));
+    }
+
+    let seed = matches.get_one::<String>("seed").expect("need seed");
+    let (signer, signature) = match algorithm.as_str() {
         "sr25519" => {
-            let pair = sr25519::Pair::from_string_with_seed(seed, None)
-                .expect("failed to parse seed")
-                .0;
-            let req = AssociateAccountRequest::Polkadot(account.to_owned(), kiltapi::kilt::runtime_types::sp_runtime::MultiSignature::Sr25519(
-                kiltapi::kilt::runtime_types::sp_core::sr25519::Signature(
-                    pair.sign(&(account, expiration).encode()).0,
-                ),
-            ));
-            kilt::tx().did_lookup().associate_account(
-                req,
-                expiration,
+            let pair =
+                sr25519::Pair::from_string(seed, None).map_err(|_| "failed to parse seed")?;
+            (
+                AccountId32(pair.public().0),
+                MultiSignature::Sr25519(types::sr25519::Signature(pair.sign(message).0)),
             )
         }
         "ed25519" => {
-            let pair = ed25519::Pair::from_string_with_seed(seed, None)
-                .expect("failed to parse seed")
-                .0;
-            let req = AssociateAccountRequest::Polkadot(account.to_owned(), kiltapi::kilt::runtime_types::sp_runtime::MultiSignature::Ed25519(
-                kiltapi::kilt::runtime_types::sp_core::ed25519::Signature(
-                    pair.sign(&(account, expiration).encode()).0,
-                ),
-            ));
-            kilt::tx().did_lookup().associate_account(
-                req,
-                expiration,
+            let pair =
+                ed25519::Pair::from_string(seed, None).map_err(|_| "failed to parse seed")?;
+            (
+                AccountId32(pair.public().0),
+                MultiSignature::Ed25519(types::ed25519::Signature(pair.sign(message).0)),
             )
         }
         "ecdsa" => {
-            let pair = ecdsa::Pair::from_string_with_seed(seed, None)
-                .expect("failed to parse seed")
-                .0;
-            let req = AssociateAccountRequest::Polkadot(account.to_owned(), kiltapi::kilt::runtime_types::sp_runtime::MultiSignature::Ecdsa(
-                kiltapi::kilt::runtime_types::sp_core::ecdsa::Signature(
-                    pair.sign(&(account, expiration).encode()).0,
-                ),
-            ));
-            kilt::tx().did_lookup().associate_account(
-                req,
-                expiration,
+            let pair = ecdsa::Pair::from_string(seed, None).map_err(|_| "failed to parse seed")?;
+            (
+                AccountId32(blake2_256(&pair.public().0)),
+                MultiSignature::Ecdsa(types::ecdsa::Signature(pair.sign(message).0)),
             )
         }
         _ => unreachable!(),
     };
+    if account.is_some_and(|account| account != signer) {
+        return Err("seed does not belong to the given account".into());
+    }
+    Ok(AssociateAccountRequest::Polkadot(signer, signature))
+}
 
-    let cli = connect(matches).await?;
-    let payload = tx.encode_call_data(&cli.metadata())?;
-    println!("0x{}", hex::encode(payload));
-    Ok(())
+fn ethereum_request(
+    matches: &clap::ArgMatches,
+    message: &[u8],
+) -> Result<AssociateAccountRequest, Box<dyn std::error::Error>> {
+    let account = match matches.get_one::<LinkableAccount>("account") {
+        Some(LinkableAccount::Ethereum(address)) => Some(*address),
+        Some(LinkableAccount::Substrate(_)) => {
+            return Err("ethereum accounts need a 0x address".into())
+        }
+        None => None,
+    };
+
+    let (address, signature) = match matches.get_one::<String>("signature") {
+        Some(signature) => (
+            account.ok_or("need account when passing a signature")?,
+            signature_bytes(signature)?,
+        ),
+        None => {
+            let seed = matches.get_one::<String>("seed").expect("need seed");
+            let path = matches.get_one::<String>("derivation-path").unwrap();
+            let key = EthereumKey::from_secret(seed, path)?;
+            if account.is_some_and(|account| account != key.address()) {
+                return Err("key does not belong to the given account".into());
+            }
+            (key.address(), key.sign(message))
+        }
+    };
+    Ok(AssociateAccountRequest::Ethereum(
+        AccountId20(address),
+        EthereumSignature(types::ecdsa::Signature(signature)),
+    ))
 }
@@ -1,4 +1,4 @@
-use kiltapi::{connect, kilt};
+use kiltapi::{connect, kilt, print_call};
 
 use subxt::tx::TxPayload;
 
@@ -10,6 +10,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let tx = kilt::tx().did_lookup().associate_sender();
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
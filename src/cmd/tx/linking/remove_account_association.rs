@@ -1,5 +1,5 @@
 use kiltapi::kilt::runtime_types::pallet_did_lookup::linkable_account::LinkableAccountId;
-use kiltapi::{connect, kilt, AccountIdParser};
+use kiltapi::{connect, kilt, print_call, AccountIdParser};
 use subxt::tx::TxPayload;
 use subxt::utils::AccountId32;
 
@@ -27,6 +27,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         .remove_account_association(id);
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
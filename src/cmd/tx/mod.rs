@@ -3,7 +3,10 @@ use clap::ArgMatches;
 mod attestation;
 mod balances;
 mod ctype;
+pub mod delegation;
 mod did;
+mod dry_run;
+mod estimate;
 mod linking;
 mod proxy;
 mod public_credentials;
@@ -21,10 +24,13 @@ pub fn command() -> clap::Command {
             balances::command(),
             sign::command(),
             submit::command(),
+            estimate::command(),
+            dry_run::command(),
             did::command(),
             ctype::command(),
             util::command(),
             attestation::command(),
+            delegation::command(),
             proxy::command(),
             linking::command(),
             public_credentials::command(),
@@ -39,9 +45,12 @@ pub async fn run(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>>
         Some(("did", matches)) => did::run(matches).await,
         Some(("sign", matches)) => sign::run(matches).await,
         Some(("submit", matches)) => submit::run(matches).await,
+        Some(("estimate", matches)) => estimate::run(matches).await,
+        Some(("dry-run", matches)) => dry_run::run(matches).await,
         Some(("ctype", matches)) => ctype::run(matches).await,
         Some(("util", matches)) => util::run(matches).await,
         Some(("attestation", matches)) => attestation::run(matches).await,
+        Some(("delegation", matches)) => delegation::run(matches).await,
         Some(("proxy", matches)) => proxy::run(matches).await,
         Some(("linking", matches)) => linking::run(matches).await,
         Some(("public-credentials", matches)) => public_credentials::run(matches).await,
@@ -1,7 +1,7 @@
 use kiltapi::{
     connect,
     kilt::{self},
-    AccountIdParser, HashParser,
+    print_call, AccountIdParser, HashParser,
 };
 use subxt::ext::sp_core::{crypto::AccountId32, H256};
 use subxt::{tx::TxPayload, utils::MultiAddress};
@@ -41,7 +41,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
 
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
@@ -2,7 +2,7 @@ use codec::Decode;
 use kiltapi::{
     connect,
     kilt::{self, ProxyType},
-    AccountIdParser, CallParser, RawCall,
+    print_call, AccountIdParser, CallParser, RawCall,
 };
 use subxt::tx::TxPayload;
 use subxt::utils::AccountId32;
@@ -81,7 +81,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
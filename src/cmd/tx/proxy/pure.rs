@@ -1,6 +1,7 @@
 use kiltapi::{
     connect,
     kilt::{self, ProxyType},
+    print_call,
 };
 use subxt::tx::TxPayload;
 
@@ -68,7 +69,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
@@ -3,33 +3,38 @@ use kiltapi::{
     kilt::runtime_types::{
         public_credentials::credentials::Credential, sp_core::bounded::bounded_vec::BoundedVec,
     },
+    print_call,
 };
 use subxt::ext::sp_core::H256;
 use subxt::tx::TxPayload;
 
+use crate::tx::delegation::{authorization, with_authorization};
+
 pub fn command() -> clap::Command {
-    clap::Command::new("add")
-        .about("Add a new public credential to the chain")
-        .arg(
-            clap::Arg::new("ctype")
-                .short('c')
-                .long("ctype")
-                .help("CType hash")
-                .env("CTYPE"),
-        )
-        .arg(
-            clap::Arg::new("subject")
-                .short('s')
-                .long("subject")
-                .help("DID subject")
-                .env("SUBJECT"),
-        )
-        .arg(
-            clap::Arg::new("claims")
-                .long("claims")
-                .help("Claims")
-                .env("CLAIMS"),
-        )
+    with_authorization(
+        clap::Command::new("add")
+            .about("Add a new public credential to the chain")
+            .arg(
+                clap::Arg::new("ctype")
+                    .short('c')
+                    .long("ctype")
+                    .help("CType hash")
+                    .env("CTYPE"),
+            )
+            .arg(
+                clap::Arg::new("subject")
+                    .short('s')
+                    .long("subject")
+                    .help("DID subject")
+                    .env("SUBJECT"),
+            )
+            .arg(
+                clap::Arg::new("claims")
+                    .long("claims")
+                    .help("Claims")
+                    .env("CLAIMS"),
+            ),
+    )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
@@ -44,16 +49,16 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         claims.into_bytes()
     };
 
+    let cli = connect(matches).await?;
     let tx = crate::kilt::tx().public_credentials().add(Credential {
         ctype_hash,
         subject: BoundedVec(did.into_bytes()),
         claims: BoundedVec(claims_bytes),
-        authorization: None,
+        authorization: authorization(matches, &cli)?,
     });
 
-    let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
@@ -1,4 +1,4 @@
-use kiltapi::{connect, unwrap_or_stdin};
+use kiltapi::{connect, print_call, unwrap_or_stdin};
 use subxt::ext::sp_core::H256;
 use subxt::tx::TxPayload;
 
@@ -22,6 +22,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
 
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
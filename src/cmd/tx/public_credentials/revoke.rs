@@ -1,16 +1,20 @@
-use kiltapi::{connect, unwrap_or_stdin};
+use kiltapi::{connect, print_call, unwrap_or_stdin};
 use subxt::ext::sp_core::H256;
 use subxt::tx::TxPayload;
 
+use crate::tx::delegation::{authorization, with_authorization};
+
 pub fn command() -> clap::Command {
-    clap::Command::new("revoke")
-        .about("Revoke an public attestation from the blockchain")
-        .arg(
-            clap::Arg::new("id")
-                .long("id")
-                .required(false) // will be read from stdin if not provided
-                .help("ID of the credential to revoke"),
-        )
+    with_authorization(
+        clap::Command::new("revoke")
+            .about("Revoke an public attestation from the blockchain")
+            .arg(
+                clap::Arg::new("id")
+                    .long("id")
+                    .required(false) // will be read from stdin if not provided
+                    .help("ID of the credential to revoke"),
+            ),
+    )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
@@ -18,10 +22,12 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let id_bytes = hex::decode(id_str.trim_start_matches("0x").trim())?;
     let id = H256::from_slice(&id_bytes);
 
-    let tx = crate::kilt::tx().public_credentials().revoke(id, None);
-
     let cli = connect(matches).await?;
+    let tx = crate::kilt::tx()
+        .public_credentials()
+        .revoke(id, authorization(matches, &cli)?);
+
     let payload = tx.encode_call_data(&cli.metadata())?;
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
@@ -1,6 +1,20 @@
-use kiltapi::{connect, unwrap_or_stdin, RawCall};
-use subxt::ext::sp_core::{sr25519, Pair};
-use subxt::{config::{polkadot::PolkadotExtrinsicParamsBuilder, substrate::Era}, tx::PairSigner};
+use clap::ArgAction;
+use codec::Decode;
+use kiltapi::{
+    connect, kilt::KiltConfig, print_output, unwrap_or_stdin, BalanceParser, ChainInfo, HashParser,
+    RawCall,
+};
+use subxt::ext::sp_core::{ecdsa, ed25519, sr25519, Pair, H256};
+use subxt::ext::sp_runtime::{MultiSignature, MultiSigner};
+use subxt::{
+    config::{
+        polkadot::{PlainTip, PolkadotExtrinsicParamsBuilder},
+        substrate::Era,
+    },
+    rpc::types::RuntimeVersion,
+    tx::PairSigner,
+    OfflineClient,
+};
 
 pub fn command() -> clap::Command {
     clap::Command::new("sign")
@@ -13,12 +27,83 @@ pub fn command() -> clap::Command {
                 .required(true)
                 .env("SEED"),
         )
+        .arg(
+            clap::Arg::new("key-type")
+                .short('k')
+                .long("key-type")
+                .help("Key type of the signing account")
+                .default_value("sr25519")
+                .value_parser(["sr25519", "ed25519", "ecdsa"])
+                .env("KEY_TYPE"),
+        )
         .arg(
             clap::Arg::new("tx")
                 .short('t')
                 .long("tx")
                 .help("Transaction to sign"),
         )
+        .arg(
+            clap::Arg::new("mortality")
+                .long("mortality")
+                .help("Number of blocks the transaction stays valid for, counted from the latest finalized block (rounded to a power of two; immortal if omitted)")
+                .value_parser(clap::value_parser!(u64).range(4..=65536))
+                .env("MORTALITY"),
+        )
+        .arg(
+            clap::Arg::new("tip")
+                .long("tip")
+                .help("Tip to pay to the block author (e.g. 1000000000 or 1KILT)")
+                .value_parser(BalanceParser)
+                .default_value("0")
+                .env("TIP"),
+        )
+        .arg(
+            clap::Arg::new("offline")
+                .long("offline")
+                .help("Sign without connecting to a node")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("nonce")
+                .long("nonce")
+                .help("Account nonce to use instead of the on-chain one (required when signing offline)")
+                .value_parser(clap::value_parser!(u64))
+                .env("NONCE"),
+        )
+        .arg(
+            clap::Arg::new("genesis-hash")
+                .long("genesis-hash")
+                .help("Genesis hash of the chain (offline signing)")
+                .value_parser(HashParser)
+                .env("GENESIS_HASH"),
+        )
+        .arg(
+            clap::Arg::new("spec-version")
+                .long("spec-version")
+                .help("Runtime spec version (offline signing)")
+                .value_parser(clap::value_parser!(u32))
+                .env("SPEC_VERSION"),
+        )
+        .arg(
+            clap::Arg::new("tx-version")
+                .long("tx-version")
+                .help("Runtime transaction version (offline signing)")
+                .value_parser(clap::value_parser!(u32))
+                .env("TX_VERSION"),
+        )
+        .arg(
+            clap::Arg::new("chain-info")
+                .long("chain-info")
+                .help("Chain info file written by `util chain-info`")
+                .env("CHAIN_INFO"),
+        )
+        .arg(
+            clap::Arg::new("metadata")
+                .long("metadata")
+                .help("Metadata file to use when signing offline")
+                .default_value("metadata.scale")
+                .env("METADATA"),
+        )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
@@ -32,19 +117,135 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let call = RawCall {
         call: hex::decode(tx.trim_start_matches("0x").trim())?,
     };
-    let pair = sr25519::Pair::from_string_with_seed(seed, None)
-        .map_err(|_| "bad seed")?
-        .0;
-    let signer = PairSigner::new(pair);
+    let key_type: &String = matches.get_one("key-type").expect("need key type");
+    let signed = match key_type.as_str() {
+        "sr25519" => {
+            let pair = sr25519::Pair::from_string_with_seed(seed, None)
+                .map_err(|_| "bad seed")?
+                .0;
+            sign(matches, &call, pair).await?
+        }
+        "ed25519" => {
+            let pair = ed25519::Pair::from_string_with_seed(seed, None)
+                .map_err(|_| "bad seed")?
+                .0;
+            sign(matches, &call, pair).await?
+        }
+        "ecdsa" => {
+            let pair = ecdsa::Pair::from_string_with_seed(seed, None)
+                .map_err(|_| "bad seed")?
+                .0;
+            sign(matches, &call, pair).await?
+        }
+        _ => unreachable!(),
+    };
 
-    let cli = connect(matches).await?;
+    let extrinsic = format!("0x{}", hex::encode(signed));
+    print_output(
+        matches,
+        &extrinsic,
+        serde_json::json!({ "extrinsic": extrinsic }),
+    );
+    Ok(())
+}
 
-    let params = PolkadotExtrinsicParamsBuilder::new()
-        // .tip(PlainTip::new(20_000_000_000_000))
-        .era(Era::Immortal, cli.genesis_hash());
+async fn sign<P>(
+    matches: &clap::ArgMatches,
+    call: &RawCall,
+    pair: P,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>>
+where
+    P: Pair,
+    MultiSigner: From<P::Public>,
+    P::Signature: Into<MultiSignature>,
+{
+    let signer = PairSigner::<KiltConfig, P>::new(pair);
 
-    let signed = cli.tx().create_signed(&call, &signer, params).await?;
+    let tip = *matches.get_one::<u128>("tip").unwrap();
+    let mortality = matches.get_one::<u64>("mortality").copied();
+    let nonce = matches.get_one::<u64>("nonce").copied();
 
-    println!("0x{}", hex::encode(signed.encoded()));
-    Ok(())
+    let signed = if matches.get_flag("offline") {
+        let chain_info = match matches.get_one::<String>("chain-info") {
+            Some(file) => Some(serde_json::from_str::<ChainInfo>(
+                &std::fs::read_to_string(file)?,
+            )?),
+            None => None,
+        };
+        let genesis_hash = matches
+            .get_one::<H256>("genesis-hash")
+            .copied()
+            .or(chain_info.as_ref().map(|info| info.genesis_hash))
+            .ok_or("offline signing needs a genesis hash")?;
+        let spec_version = matches
+            .get_one::<u32>("spec-version")
+            .copied()
+            .or(chain_info.as_ref().map(|info| info.spec_version))
+            .ok_or("offline signing needs a spec version")?;
+        let transaction_version = matches
+            .get_one::<u32>("tx-version")
+            .copied()
+            .or(chain_info.as_ref().map(|info| info.transaction_version))
+            .ok_or("offline signing needs a transaction version")?;
+        let nonce = nonce
+            .or(chain_info.as_ref().and_then(|info| info.nonce))
+            .ok_or("offline signing needs a nonce")?;
+
+        let (era, checkpoint) = match mortality {
+            Some(period) => {
+                let (hash, number) = chain_info
+                    .as_ref()
+                    .and_then(|info| info.block_hash.zip(info.block_number))
+                    .ok_or("offline mortal transactions need a checkpoint block from `util chain-info`")?;
+                (Era::mortal(period, number), hash)
+            }
+            None => (Era::Immortal, genesis_hash),
+        };
+
+        let metadata_file: &String = matches.get_one("metadata").expect("need metadata");
+        let metadata = subxt::Metadata::decode(&mut &std::fs::read(metadata_file)?[..])?;
+
+        let cli = OfflineClient::<KiltConfig>::new(
+            genesis_hash,
+            RuntimeVersion {
+                spec_version,
+                transaction_version,
+                other: Default::default(),
+            },
+            metadata,
+        );
+
+        let params = PolkadotExtrinsicParamsBuilder::new()
+            .tip(PlainTip::new(tip))
+            .era(era, checkpoint);
+
+        cli.tx()
+            .create_signed_with_nonce(call, &signer, nonce, params)?
+            .into_encoded()
+    } else {
+        let cli = connect(matches).await?;
+
+        let (era, checkpoint) = match mortality {
+            Some(period) => {
+                let hash = cli.rpc().finalized_head().await?;
+                let number = cli.blocks().at(hash).await?.number();
+                (Era::mortal(period, number), hash)
+            }
+            None => (Era::Immortal, cli.genesis_hash()),
+        };
+
+        let params = PolkadotExtrinsicParamsBuilder::new()
+            .tip(PlainTip::new(tip))
+            .era(era, checkpoint);
+
+        match nonce {
+            Some(nonce) => cli
+                .tx()
+                .create_signed_with_nonce(call, &signer, nonce, params)?,
+            None => cli.tx().create_signed(call, &signer, params).await?,
+        }
+        .into_encoded()
+    };
+
+    Ok(signed)
 }
@@ -1,4 +1,4 @@
-use kiltapi::{connect, kilt, BalanceParser};
+use kiltapi::{connect, kilt, print_call, BalanceParser};
 use subxt::tx::TxPayload;
 
 pub fn command() -> clap::Command {
@@ -24,7 +24,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
@@ -1,8 +1,10 @@
-use kiltapi::{connect, kilt};
+use kiltapi::{connect, kilt, print_call};
 use subxt::tx::TxPayload;
 
 pub fn command() -> clap::Command {
-    clap::Command::new("claim-rewards").about("Claim your staking rewards")
+    clap::Command::new("claim-rewards").about(
+        "Claim the staking rewards of the sender (use tx proxy to claim for another account)",
+    )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
@@ -11,7 +13,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
@@ -1,4 +1,4 @@
-use kiltapi::{connect, BalanceParser, kilt};
+use kiltapi::{connect, kilt, print_call, BalanceParser};
 use subxt::tx::TxPayload;
 
 pub fn command() -> clap::Command {
@@ -8,7 +8,7 @@ pub fn command() -> clap::Command {
             clap::Arg::new("amount")
                 .short('a')
                 .long("amount")
-                .help("Amount of tokens to stake")
+                .help("Amount of tokens to unstake")
                 .required(true)
                 .value_parser(BalanceParser),
         )
@@ -17,12 +17,14 @@ pub fn command() -> clap::Command {
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
     let amount = matches.get_one::<u128>("amount").unwrap();
 
-    let tx = kilt::tx().parachain_staking().delegator_stake_less(amount.to_owned());
+    let tx = kilt::tx()
+        .parachain_staking()
+        .delegator_stake_less(amount.to_owned());
 
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
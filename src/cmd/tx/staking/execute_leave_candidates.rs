@@ -1,4 +1,4 @@
-use kiltapi::{connect, kilt, AccountIdParser};
+use kiltapi::{connect, kilt, print_call, AccountIdParser};
 use subxt::tx::TxPayload;
 use subxt::utils::AccountId32;
 
@@ -25,7 +25,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
@@ -1,4 +1,4 @@
-use kiltapi::{connect, kilt};
+use kiltapi::{connect, kilt, print_call};
 use subxt::tx::TxPayload;
 
 pub fn command() -> clap::Command {
@@ -11,7 +11,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
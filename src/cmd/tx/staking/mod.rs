@@ -1,15 +1,24 @@
 mod cancel_leave_candidates;
 mod candidate_stake_less;
+mod candidate_stake_more;
 mod claim_rewards;
+mod delegator_stake_less;
+mod delegator_stake_more;
 mod execute_leave_candidates;
 mod execute_scheduled_rewards_change;
 mod force_new_round;
 mod force_remove_candidate;
 mod increment_collator_rewards;
+mod increment_delegator_rewards;
 mod init_leave_candidates;
 mod join_candidates;
 mod join_delegators;
 mod leave_delegators;
+mod set_blocks_per_round;
+mod set_inflation;
+mod set_max_candidate_stake;
+mod set_max_selected_candidates;
+mod unlock_unstaked;
 
 pub fn command() -> clap::Command {
     clap::Command::new("staking")
@@ -17,25 +26,37 @@ pub fn command() -> clap::Command {
         .subcommand_required(true)
         .subcommands([
             candidate_stake_less::command(),
+            candidate_stake_more::command(),
             cancel_leave_candidates::command(),
             claim_rewards::command(),
+            delegator_stake_less::command(),
+            delegator_stake_more::command(),
             execute_leave_candidates::command(),
             execute_scheduled_rewards_change::command(),
             force_new_round::command(),
             force_remove_candidate::command(),
             increment_collator_rewards::command(),
+            increment_delegator_rewards::command(),
             init_leave_candidates::command(),
             join_candidates::command(),
             join_delegators::command(),
             leave_delegators::command(),
+            set_blocks_per_round::command(),
+            set_inflation::command(),
+            set_max_candidate_stake::command(),
+            set_max_selected_candidates::command(),
+            unlock_unstaked::command(),
         ])
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
     match matches.subcommand() {
         Some(("candidate-stake-less", matches)) => candidate_stake_less::run(matches).await,
+        Some(("candidate-stake-more", matches)) => candidate_stake_more::run(matches).await,
         Some(("cancel-leave-candidates", matches)) => cancel_leave_candidates::run(matches).await,
         Some(("claim-rewards", matches)) => claim_rewards::run(matches).await,
+        Some(("delegator-stake-less", matches)) => delegator_stake_less::run(matches).await,
+        Some(("delegator-stake-more", matches)) => delegator_stake_more::run(matches).await,
         Some(("execute-leave-candidates", matches)) => execute_leave_candidates::run(matches).await,
         Some(("execute-scheduled-rewards-change", matches)) => {
             execute_scheduled_rewards_change::run(matches).await
@@ -45,10 +66,20 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         Some(("increment-collator-rewards", matches)) => {
             increment_collator_rewards::run(matches).await
         }
+        Some(("increment-delegator-rewards", matches)) => {
+            increment_delegator_rewards::run(matches).await
+        }
         Some(("init-leave-candidates", matches)) => init_leave_candidates::run(matches).await,
         Some(("join-candidates", matches)) => join_candidates::run(matches).await,
         Some(("join-delegators", matches)) => join_delegators::run(matches).await,
         Some(("leave-delegators", matches)) => leave_delegators::run(matches).await,
+        Some(("set-blocks-per-round", matches)) => set_blocks_per_round::run(matches).await,
+        Some(("set-inflation", matches)) => set_inflation::run(matches).await,
+        Some(("set-max-candidate-stake", matches)) => set_max_candidate_stake::run(matches).await,
+        Some(("set-max-selected-candidates", matches)) => {
+            set_max_selected_candidates::run(matches).await
+        }
+        Some(("unlock-unstaked", matches)) => unlock_unstaked::run(matches).await,
         _ => unreachable!(),
     }
 }
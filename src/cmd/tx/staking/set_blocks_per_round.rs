@@ -0,0 +1,30 @@
+use kiltapi::{connect, kilt, print_call};
+use subxt::tx::TxPayload;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("set-blocks-per-round")
+        .about("Set the length of a staking round (root only)")
+        .arg(
+            clap::Arg::new("blocks")
+                .short('b')
+                .long("blocks")
+                .help("Number of blocks per round")
+                .required(true)
+                .value_parser(clap::value_parser!(u64)),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let blocks = matches.get_one::<u64>("blocks").unwrap();
+
+    let tx = kilt::tx()
+        .parachain_staking()
+        .set_blocks_per_round(blocks.to_owned());
+
+    let cli = connect(matches).await?;
+    let payload = tx.encode_call_data(&cli.metadata())?;
+
+    print_call(matches, &payload);
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use kiltapi::{
+    connect,
+    kilt::{self, runtime_types::sp_arithmetic::per_things::Perquintill},
+    print_call,
+};
+use subxt::tx::TxPayload;
+
+pub fn command() -> clap::Command {
+    let rate = |name: &'static str, help: &'static str| {
+        clap::Arg::new(name)
+            .long(name)
+            .help(help)
+            .required(true)
+            .value_parser(parse_percent)
+    };
+    clap::Command::new("set-inflation")
+        .about("Set the inflation configuration (root only)")
+        .arg(rate(
+            "collator-max-rate",
+            "Maximum staking rate of collators in percent",
+        ))
+        .arg(rate(
+            "collator-reward-rate",
+            "Annual reward rate of collators in percent",
+        ))
+        .arg(rate(
+            "delegator-max-rate",
+            "Maximum staking rate of delegators in percent",
+        ))
+        .arg(rate(
+            "delegator-reward-rate",
+            "Annual reward rate of delegators in percent",
+        ))
+}
+
+// Parse a percentage like `10` or `0.5` into parts per quintillion (1% = 10^16 parts).
+fn parse_percent(value: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid percentage {value}");
+    let (int, frac) = value.trim().split_once('.').unwrap_or((value.trim(), ""));
+    if frac.len() > 16 || !frac.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let frac = format!("{frac:0<16}")
+        .parse::<u64>()
+        .map_err(|_| invalid())?;
+    let parts = int
+        .parse::<u64>()
+        .ok()
+        .and_then(|int| int.checked_mul(10u64.pow(16)))
+        .and_then(|int| int.checked_add(frac))
+        .ok_or_else(invalid)?;
+    if parts > 10u64.pow(18) {
+        return Err(invalid());
+    }
+    Ok(parts)
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let rate = |name: &str| Perquintill(*matches.get_one::<u64>(name).unwrap());
+
+    let tx = kilt::tx().parachain_staking().set_inflation(
+        rate("collator-max-rate"),
+        rate("collator-reward-rate"),
+        rate("delegator-max-rate"),
+        rate("delegator-reward-rate"),
+    );
+
+    let cli = connect(matches).await?;
+    let payload = tx.encode_call_data(&cli.metadata())?;
+
+    print_call(matches, &payload);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use kiltapi::{connect, kilt, print_call, BalanceParser};
+use subxt::tx::TxPayload;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("set-max-candidate-stake")
+        .about("Set the maximum amount a collator can stake (root only)")
+        .arg(
+            clap::Arg::new("amount")
+                .short('a')
+                .long("amount")
+                .help("Maximum stake of a collator")
+                .required(true)
+                .value_parser(BalanceParser),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let amount = matches.get_one::<u128>("amount").unwrap();
+
+    let tx = kilt::tx()
+        .parachain_staking()
+        .set_max_candidate_stake(amount.to_owned());
+
+    let cli = connect(matches).await?;
+    let payload = tx.encode_call_data(&cli.metadata())?;
+
+    print_call(matches, &payload);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use kiltapi::{connect, kilt, print_call};
+use subxt::tx::TxPayload;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("set-max-selected-candidates")
+        .about("Set the maximum number of collators selected each round (root only)")
+        .arg(
+            clap::Arg::new("count")
+                .short('c')
+                .long("count")
+                .help("Number of collators")
+                .required(true)
+                .value_parser(clap::value_parser!(u32)),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let count = matches.get_one::<u32>("count").unwrap();
+
+    let tx = kilt::tx()
+        .parachain_staking()
+        .set_max_selected_candidates(count.to_owned());
+
+    let cli = connect(matches).await?;
+    let payload = tx.encode_call_data(&cli.metadata())?;
+
+    print_call(matches, &payload);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use kiltapi::{connect, kilt, print_call, AccountIdParser};
+use subxt::tx::TxPayload;
+use subxt::utils::AccountId32;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("unlock-unstaked")
+        .about("Unlock all previously staked funds that are now available")
+        .arg(
+            clap::Arg::new("account")
+                .short('a')
+                .long("account")
+                .help("Account to unlock the funds of")
+                .required(true)
+                .value_parser(AccountIdParser)
+                .env("ACCOUNT"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let account = matches.get_one::<AccountId32>("account").unwrap();
+
+    let tx = kilt::tx()
+        .parachain_staking()
+        .unlock_unstaked(account.to_owned().into());
+
+    let cli = connect(matches).await?;
+    let payload = tx.encode_call_data(&cli.metadata())?;
+
+    print_call(matches, &payload);
+
+    Ok(())
+}
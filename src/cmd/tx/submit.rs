@@ -1,8 +1,16 @@
 use std::str::FromStr;
 
 use clap::ArgMatches;
-use kiltapi::{connect, kilt::KiltConfig, unwrap_or_stdin};
-use subxt::tx::SubmittableExtrinsic;
+use kiltapi::{
+    connect, format_dispatch_error,
+    kilt::{self, KiltConfig},
+    output_json, unwrap_or_stdin,
+};
+use subxt::{
+    blocks::ExtrinsicEvents,
+    ext::scale_value::{Composite, Primitive, Value, ValueDef},
+    tx::SubmittableExtrinsic,
+};
 
 pub fn command() -> clap::Command {
     clap::Command::new("submit")
@@ -51,20 +59,22 @@ pub async fn run(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>>
 
     let cli = connect(matches).await?;
 
+    let metadata = cli.metadata();
     let tx = SubmittableExtrinsic::from_bytes(cli, tx);
-    submit_extrinsic(tx, wait_for).await?;
+    submit_extrinsic(tx, wait_for, &metadata, output_json(matches)).await?;
     Ok(())
 }
 
 async fn submit_extrinsic(
     tx: SubmittableExtrinsic<KiltConfig, subxt::OnlineClient<KiltConfig>>,
     wait_for: WaitFor,
+    metadata: &subxt::Metadata,
+    json: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let mut events_checked = false;
     let mut progress = tx.submit_and_watch().await?;
-    log::info!(
-        "Submitted Extrinsic with hash {:?}",
-        progress.extrinsic_hash()
-    );
+    let extrinsic_hash = progress.extrinsic_hash();
+    log::info!("Submitted Extrinsic with hash {:?}", extrinsic_hash);
     while let Some(Ok(status)) = progress.next_item().await {
         match status {
             subxt::tx::TxStatus::Future => {
@@ -76,22 +86,20 @@ async fn submit_extrinsic(
             subxt::tx::TxStatus::Broadcast(peers) => {
                 log::info!("Extrinsic broadcasted to {:?}", peers);
                 if wait_for == WaitFor::Submitted {
+                    if json {
+                        println!(
+                            "{}",
+                            serde_json::json!({ "extrinsicHash": format!("{extrinsic_hash:?}") })
+                        );
+                    }
                     return Ok(());
                 }
             }
             subxt::tx::TxStatus::InBlock(status) => {
                 log::info!("Extrinsic included in block {:?}", status.block_hash());
                 let events = status.fetch_events().await?;
-                events.iter().for_each(|e| {
-                    if let Ok(e) = e {
-                        log::info!(
-                            "{}.{}: {:#?}",
-                            e.pallet_name(),
-                            e.variant_name(),
-                            e.event_metadata().pallet.docs()
-                        );
-                    }
-                });
+                check_events(&events, metadata, json)?;
+                events_checked = true;
                 if wait_for == WaitFor::InBlock {
                     return Ok(());
                 }
@@ -101,23 +109,111 @@ async fn submit_extrinsic(
             }
             subxt::tx::TxStatus::Finalized(status) => {
                 log::info!("Extrinsic finalized in block {:?}", status.block_hash());
+                if !events_checked {
+                    let events = status.fetch_events().await?;
+                    check_events(&events, metadata, json)?;
+                }
                 if wait_for == WaitFor::Finalized {
                     return Ok(());
                 }
             }
             subxt::tx::TxStatus::Usurped(hash) => {
-                log::info!("Extrinsic usurped in block {:?}", hash);
+                return Err(format!("Extrinsic usurped in block {hash:?}").into());
             }
             subxt::tx::TxStatus::Dropped => {
-                log::info!("Extrinsic dropped");
+                return Err("Extrinsic dropped".into());
             }
             subxt::tx::TxStatus::Invalid => {
-                log::info!("Extrinsic invalid");
+                return Err("Extrinsic invalid".into());
             }
             subxt::tx::TxStatus::FinalityTimeout(hash) => {
-                log::info!("Extrinsic finality timeout in block {:?}", hash);
+                return Err(format!("Extrinsic finality timeout in block {hash:?}").into());
             }
         }
     }
     Ok(())
 }
+
+// Print all events emitted by the extrinsic and fail if it could not be dispatched.
+fn check_events(
+    events: &ExtrinsicEvents<KiltConfig>,
+    metadata: &subxt::Metadata,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut decoded = vec![];
+    let mut error = None;
+    for event in events.iter() {
+        let event = event?;
+        let fields = event.field_values()?;
+        if !json {
+            println!(
+                "{}.{} {}",
+                event.pallet_name(),
+                event.variant_name(),
+                fields
+            );
+        }
+        decoded.push(serde_json::json!({
+            "pallet": event.pallet_name(),
+            "event": event.variant_name(),
+            "fields": composite_json(&fields),
+        }));
+        if let Some(failed) = event.as_event::<kilt::system::events::ExtrinsicFailed>()? {
+            error = Some(format_dispatch_error(&failed.dispatch_error, metadata));
+        }
+    }
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "extrinsicHash": format!("{:?}", events.extrinsic_hash()),
+                "blockHash": format!("{:?}", events.block_hash()),
+                "success": error.is_none(),
+                "error": error,
+                "events": decoded,
+            })
+        );
+    }
+    match error {
+        Some(error) => Err(format!("Extrinsic failed: {error}").into()),
+        None => Ok(()),
+    }
+}
+
+// Convert decoded event fields to JSON. This follows the serde representation of scale-value, but
+// numbers that do not fit into 64 bits (like balances) are printed as strings.
+fn composite_json<T>(composite: &Composite<T>) -> serde_json::Value {
+    match composite {
+        Composite::Named(fields) => fields
+            .iter()
+            .map(|(name, value)| (name.clone(), value_json(value)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        Composite::Unnamed(values) => values.iter().map(value_json).collect(),
+    }
+}
+
+fn value_json<T>(value: &Value<T>) -> serde_json::Value {
+    match &value.value {
+        ValueDef::Composite(composite) => composite_json(composite),
+        ValueDef::Variant(variant) => serde_json::json!({
+            "name": variant.name,
+            "values": composite_json(&variant.values),
+        }),
+        ValueDef::BitSequence(bits) => bits.iter().collect(),
+        ValueDef::Primitive(primitive) => match primitive {
+            Primitive::Bool(b) => (*b).into(),
+            Primitive::Char(c) => c.to_string().into(),
+            Primitive::String(s) => s.clone().into(),
+            Primitive::U128(n) => match u64::try_from(*n) {
+                Ok(n) => n.into(),
+                Err(_) => n.to_string().into(),
+            },
+            Primitive::I128(n) => match i64::try_from(*n) {
+                Ok(n) => n.into(),
+                Err(_) => n.to_string().into(),
+            },
+            Primitive::U256(n) | Primitive::I256(n) => format!("0x{}", hex::encode(n)).into(),
+        },
+    }
+}
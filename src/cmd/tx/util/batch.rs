@@ -1,6 +1,6 @@
 use clap::ArgAction;
 use codec::Decode;
-use kiltapi::{connect, kilt::RuntimeCall};
+use kiltapi::{connect, kilt::RuntimeCall, print_call};
 use subxt::tx::TxPayload;
 
 pub fn command() -> clap::Command {
@@ -70,7 +70,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         _ => unreachable!(),
     };
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
 
     Ok(())
 }
@@ -1,4 +1,4 @@
-use kiltapi::connect;
+use kiltapi::{connect, print_call};
 use subxt::tx::TxPayload;
 
 pub fn command() -> clap::Command {
@@ -11,6 +11,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
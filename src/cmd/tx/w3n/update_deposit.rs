@@ -1,5 +1,6 @@
 use kiltapi::{
-    connect, kilt::runtime_types::sp_core::bounded::bounded_vec::BoundedVec, unwrap_or_stdin,
+    connect, kilt::runtime_types::sp_core::bounded::bounded_vec::BoundedVec, print_call,
+    unwrap_or_stdin,
 };
 use subxt::tx::TxPayload;
 
@@ -25,6 +26,6 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     let cli = connect(matches).await?;
     let payload = tx.encode_call_data(&cli.metadata())?;
 
-    println!("0x{}", hex::encode(payload));
+    print_call(matches, &payload);
     Ok(())
 }
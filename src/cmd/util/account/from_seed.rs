@@ -1,4 +1,4 @@
-use kiltapi::unwrap_or_stdin;
+use kiltapi::{print_output, unwrap_or_stdin};
 use subxt::ext::sp_core::{
     crypto::{Ss58AddressFormat, Ss58Codec},
     ecdsa, ed25519, sr25519, Pair,
@@ -72,7 +72,7 @@ pub fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>>
         _ => panic!("unknown key type"),
     };
 
-    println!("{address}");
+    print_output(matches, &address, serde_json::json!({ "address": address }));
 
     Ok(())
 }
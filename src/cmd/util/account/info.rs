@@ -1,4 +1,4 @@
-use kiltapi::{connect, format_balance, AccountIdParser};
+use kiltapi::{connect, format_balance, output_json, storage_at, AccountIdParser};
 use subxt::ext::sp_core;
 use subxt::ext::sp_core::crypto::Ss58Codec;
 use subxt::utils::AccountId32;
@@ -23,18 +23,32 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
 
     let cli = connect(matches).await?;
 
-    let details = cli
-        .storage()
-        .at_latest()
+    let details = storage_at(matches, &cli)
         .await?
-        .fetch(&addr)
-        .await?
-        .expect("not found");
+        .fetch_or_default(&addr)
+        .await?;
+
+    let account_id =
+        sp_core::crypto::AccountId32::from(account.0).to_ss58check_with_version(38u16.into());
+
+    if output_json(matches) {
+        println!(
+            "{}",
+            serde_json::json!({
+                "account": account_id,
+                "free": details.data.free.to_string(),
+                "reserved": details.data.reserved.to_string(),
+                "miscFrozen": details.data.misc_frozen.to_string(),
+                "nonce": details.nonce,
+                "consumers": details.consumers,
+                "providers": details.providers,
+                "sufficients": details.sufficients,
+            })
+        );
+        return Ok(());
+    }
 
-    println!(
-        "Account ID: {}",
-        sp_core::crypto::AccountId32::from(account.0).to_ss58check_with_version(38u16.into())
-    );
+    println!("Account ID: {account_id}");
     println!("Free: {}", format_balance(details.data.free));
     println!("Reserved: {}", format_balance(details.data.reserved));
     println!("Misc Frozen: {}", format_balance(details.data.misc_frozen));
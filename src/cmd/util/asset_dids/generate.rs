@@ -1,3 +1,5 @@
+use kiltapi::print_output;
+
 pub fn command() -> clap::Command {
     clap::Command::new("generate")
         .about("Generate a valid asset-did")
@@ -45,7 +47,7 @@ pub fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>>
         did = format!("{did}:{asset_id}");
     }
 
-    println!("{did}");
+    print_output(matches, &did, serde_json::json!({ "assetDid": did }));
 
     Ok(())
 }
@@ -1,73 +1,89 @@
 use blake2::{digest::consts::U32, Blake2b, Digest};
 use codec::Encode;
 use kiltapi::{
+    connect,
     kilt::runtime_types::{
-        delegation::access_control::DelegationAc, public_credentials::credentials::Credential,
-        runtime_common::authorization::PalletAuthorize, sp_core::bounded::bounded_vec::BoundedVec,
-        spiritnet_runtime::Runtime,
+        public_credentials::credentials::Credential, sp_core::bounded::bounded_vec::BoundedVec,
     },
-    AccountIdParser,
+    print_output, resolve_did, DidArg, DidParser,
 };
 use subxt::ext::sp_core::H256;
-use subxt::utils::AccountId32;
+
+use crate::tx::delegation::{authorization, with_authorization};
 
 type Blake2b256 = Blake2b<U32>;
 
 pub fn command() -> clap::Command {
-    clap::Command::new("get-credential-id")
-        .about("Compute id for a public credential")
-        .arg(
-            clap::Arg::new("ctype")
-                .short('c')
-                .long("ctype")
-                .help("CType hash")
-                .env("CTYPE"),
-        )
-        .arg(
-            clap::Arg::new("subject")
-                .short('s')
-                .long("subject")
-                .help("DID subject")
-                .env("SUBJECT"),
-        )
-        .arg(
-            clap::Arg::new("claims")
-                .long("claims")
-                .help("Claims")
-                .env("CLAIMS"),
-        )
-        .arg(
-            clap::Arg::new("attester")
-                .long("attester")
-                .help("Attester")
-                .value_parser(AccountIdParser)
-                .env("ATTESTER"),
-        )
+    with_authorization(
+        clap::Command::new("get-credential-id")
+            .about("Compute id for a public credential")
+            .arg(
+                clap::Arg::new("ctype")
+                    .short('c')
+                    .long("ctype")
+                    .help("CType hash")
+                    .env("CTYPE"),
+            )
+            .arg(
+                clap::Arg::new("subject")
+                    .short('s')
+                    .long("subject")
+                    .help("DID subject")
+                    .env("SUBJECT"),
+            )
+            .arg(
+                clap::Arg::new("claims")
+                    .long("claims")
+                    .help("Claims")
+                    .env("CLAIMS"),
+            )
+            .arg(
+                clap::Arg::new("attester")
+                    .long("attester")
+                    .help("Attester")
+                    .value_parser(DidParser)
+                    .env("ATTESTER"),
+            ),
+    )
 }
 
-pub fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
     let ctype_hash_str = matches.get_one::<String>("ctype").unwrap().to_owned();
     let ctype_hash_bytes = hex::decode(ctype_hash_str.trim_start_matches("0x").trim())?;
     let ctype_hash = H256::from_slice(&ctype_hash_bytes);
     let did = matches.get_one::<String>("subject").unwrap().to_owned();
     let claims = matches.get_one::<String>("claims").unwrap().to_owned();
-    let attester = matches
-        .get_one::<AccountId32>("attester")
-        .unwrap()
-        .to_owned();
+    // only web3 names and delegation authorizations need a connection to the chain
+    let (attester, authorization) = match matches.get_one::<DidArg>("attester").unwrap() {
+        DidArg::Account(attester) if !matches.contains_id("authorization") => {
+            (attester.clone(), None)
+        }
+        attester => {
+            let cli = connect(matches).await?;
+            (
+                resolve_did(matches, &cli, attester).await?,
+                authorization(matches, &cli)?,
+            )
+        }
+    };
 
     let cred = Credential {
         ctype_hash,
         subject: BoundedVec(did.into_bytes()),
         claims: BoundedVec(claims.into()),
-        authorization: None::<PalletAuthorize<DelegationAc<Runtime>>>,
+        authorization,
     };
 
     let mut hasher = Blake2b256::new();
     hasher.update(&(cred, attester).encode());
     let result = hasher.finalize();
 
-    println!("0x{}", hex::encode(result));
+    let credential_id = format!("0x{}", hex::encode(result));
+    print_output(
+        matches,
+        &credential_id,
+        serde_json::json!({ "credentialId": credential_id }),
+    );
 
     Ok(())
 }
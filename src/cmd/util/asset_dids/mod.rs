@@ -8,10 +8,10 @@ pub fn command() -> clap::Command {
         .subcommands([generate::command(), get_credential_id::command()])
 }
 
-pub fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
     match matches.subcommand() {
         Some(("generate", matches)) => generate::run(matches),
-        Some(("get-credential-id", matches)) => get_credential_id::run(matches),
+        Some(("get-credential-id", matches)) => get_credential_id::run(matches).await,
         _ => unreachable!(),
     }
 }
@@ -0,0 +1,51 @@
+use kiltapi::{connect, output_json, AccountIdParser, ChainInfo};
+use subxt::utils::AccountId32;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("chain-info")
+        .about("Fetch the chain parameters needed for offline signing")
+        .arg(
+            clap::Arg::new("account")
+                .short('a')
+                .long("account")
+                .help("Account to include the next nonce for")
+                .value_parser(AccountIdParser)
+                .env("ACCOUNT"),
+        )
+        .arg(
+            clap::Arg::new("file")
+                .short('f')
+                .long("file")
+                .help("Output file")
+                .default_value("chain-info.json"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let file = matches.get_one::<String>("file").unwrap();
+    let cli = connect(matches).await?;
+    let version = cli.runtime_version();
+    let nonce = match matches.get_one::<AccountId32>("account") {
+        Some(account) => Some(
+            cli.tx()
+                .account_nonce(&subxt::ext::sp_core::crypto::AccountId32::from(account.0))
+                .await?,
+        ),
+        None => None,
+    };
+    let block_hash = cli.rpc().finalized_head().await?;
+    let block_number = cli.blocks().at(block_hash).await?.number();
+    let info = ChainInfo {
+        genesis_hash: cli.genesis_hash(),
+        spec_version: version.spec_version,
+        transaction_version: version.transaction_version,
+        nonce,
+        block_hash: Some(block_hash),
+        block_number: Some(block_number),
+    };
+    std::fs::write(file, serde_json::to_string_pretty(&info)?)?;
+    if output_json(matches) {
+        println!("{}", serde_json::json!({ "file": file }));
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use kiltapi::connect;
+use kiltapi::{connect, print_output};
 
 pub fn command() -> clap::Command {
     clap::Command::new("current-block").about("Get the latest block number")
@@ -6,7 +6,7 @@ pub fn command() -> clap::Command {
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
     let cli = connect(matches).await?;
-    let block = cli.blocks().at_latest().await?.number().to_string();
-    println!("{block}");
+    let block = cli.blocks().at_latest().await?.number();
+    print_output(matches, block, serde_json::json!({ "block": block }));
     Ok(())
 }
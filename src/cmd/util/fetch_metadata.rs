@@ -1,5 +1,5 @@
 use codec::{Decode, Encode};
-use kiltapi::connect;
+use kiltapi::{connect, output_json};
 use subxt::ext::sp_core::Bytes;
 use subxt::{ext::frame_metadata::RuntimeMetadataPrefixed, rpc::RpcParams};
 
@@ -7,16 +7,17 @@ pub fn command() -> clap::Command {
     clap::Command::new("fetch-metadata")
         .about("Fetch metadata from a node")
         .arg(
-            clap::Arg::new("output")
-                .short('o')
-                .long("output")
+            clap::Arg::new("file")
+                .short('f')
+                .long("file")
+                .visible_alias("out")
                 .help("Output file")
                 .default_value("metadata.scale"),
         )
 }
 
 pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
-    let output = matches.get_one::<String>("output").unwrap();
+    let file = matches.get_one::<String>("file").unwrap();
     let cli = connect(matches).await?;
     let bytes: Bytes = cli
         .rpc()
@@ -27,6 +28,9 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         "Fetched metadata from node {}",
         matches.get_one::<String>("endpoint").unwrap()
     );
-    std::fs::write(output, meta.encode())?;
+    std::fs::write(file, meta.encode())?;
+    if output_json(matches) {
+        println!("{}", serde_json::json!({ "file": file }));
+    }
     Ok(())
 }
@@ -1,4 +1,4 @@
-use kiltapi::unwrap_or_stdin;
+use kiltapi::{print_output, unwrap_or_stdin};
 
 use blake2::{digest::consts::U32, Blake2b, Digest};
 type Blake2b256 = Blake2b<U32>;
@@ -37,6 +37,7 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
     hasher.update(&data);
     let result = hasher.finalize();
 
-    println!("0x{}", hex::encode(result));
+    let hash = format!("0x{}", hex::encode(result));
+    print_output(matches, &hash, serde_json::json!({ "hash": hash }));
     Ok(())
 }
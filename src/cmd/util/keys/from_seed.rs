@@ -1,4 +1,4 @@
-use kiltapi::unwrap_or_stdin;
+use kiltapi::{print_output, unwrap_or_stdin};
 use subxt::ext::sp_core::{ecdsa, ed25519, sr25519, Pair};
 
 pub fn command() -> clap::Command {
@@ -63,7 +63,12 @@ pub fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>>
         _ => panic!("unknown key type"),
     };
 
-    println!("0x{}", hex::encode(key_bytes));
+    let public_key = format!("0x{}", hex::encode(key_bytes));
+    print_output(
+        matches,
+        &public_key,
+        serde_json::json!({ "type": key_type, "publicKey": public_key }),
+    );
 
     Ok(())
 }
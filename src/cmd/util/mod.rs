@@ -1,5 +1,6 @@
 mod account;
 mod asset_dids;
+mod chain_info;
 mod current_block;
 mod fetch_metadata;
 mod hash;
@@ -15,6 +16,7 @@ pub fn command() -> clap::Command {
             keys::command(),
             seed::command(),
             fetch_metadata::command(),
+            chain_info::command(),
             hash::command(),
             current_block::command(),
             asset_dids::command(),
@@ -27,9 +29,10 @@ pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::E
         Some(("keys", matches)) => keys::run(matches),
         Some(("seed", matches)) => seed::run(matches),
         Some(("fetch-metadata", matches)) => fetch_metadata::run(matches).await,
+        Some(("chain-info", matches)) => chain_info::run(matches).await,
         Some(("hash", matches)) => hash::run(matches).await,
         Some(("current-block", matches)) => current_block::run(matches).await,
-        Some(("asset-dids", matches)) => asset_dids::run(matches),
+        Some(("asset-dids", matches)) => asset_dids::run(matches).await,
         _ => unreachable!(),
     }
 }
@@ -1,4 +1,5 @@
 use bip39::{Language, Mnemonic, MnemonicType};
+use kiltapi::print_output;
 
 pub fn command() -> clap::Command {
     clap::Command::new("generate")
@@ -17,6 +18,10 @@ pub fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>>
     let words = words.parse::<usize>().expect("need words");
     let m_type = MnemonicType::for_word_count(words)?;
     let mnemonic = Mnemonic::new(m_type, Language::English);
-    println!("{}", mnemonic.phrase());
+    print_output(
+        matches,
+        mnemonic.phrase(),
+        serde_json::json!({ "seed": mnemonic.phrase() }),
+    );
     Ok(())
 }
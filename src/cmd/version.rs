@@ -1,8 +1,24 @@
+use kiltapi::output_json;
+
 pub fn command() -> clap::Command {
     clap::Command::new("version").about("Show version")
 }
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+pub fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if output_json(matches) {
+        println!(
+            "{}",
+            serde_json::json!({
+                "semver": std::env::var("VERGEN_BUILD_SEMVER")?,
+                "buildTime": std::env::var("VERGEN_BUILD_TIMESTAMP")?,
+                "gitSemver": std::env::var("VERGEN_GIT_SEMVER")?,
+                "gitSha": std::env::var("VERGEN_GIT_SHA")?,
+                "gitBranch": std::env::var("VERGEN_GIT_BRANCH")?,
+                "gitCommitDate": std::env::var("VERGEN_GIT_COMMIT_TIMESTAMP")?,
+            })
+        );
+        return Ok(());
+    }
     println!("Semver: {}", std::env::var("VERGEN_BUILD_SEMVER")?);
     println!("Build time: {}", std::env::var("VERGEN_BUILD_TIMESTAMP")?);
     println!("Git Semver: {}", std::env::var("VERGEN_GIT_SEMVER")?);
@@ -0,0 +1,94 @@
+use kiltapi::{
+    connect,
+    did::did_uri,
+    kilt::{
+        self,
+        runtime_types::{
+            pallet_web3_names::web3_name::AsciiWeb3Name, sp_core::bounded::bounded_vec::BoundedVec,
+        },
+    },
+    print_output, storage_at, web3_name_owner,
+};
+
+pub fn command() -> clap::Command {
+    clap::Command::new("check")
+        .about("Check whether a web3 name is valid and available")
+        .arg(
+            clap::Arg::new("name")
+                .short('n')
+                .long("name")
+                .help("web3 name to check")
+                .required(true)
+                .env("NAME"),
+        )
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let name = matches.get_one::<String>("name").unwrap();
+    let name = name.trim_start_matches("w3n:");
+
+    let cli = connect(matches).await?;
+    let min = cli
+        .constants()
+        .at(&kilt::constants().web3_names().min_name_length())?;
+    let max = cli
+        .constants()
+        .at(&kilt::constants().web3_names().max_name_length())?;
+
+    if let Err(reason) = validate(name, min, max) {
+        print_output(
+            matches,
+            format!("{name} is not a valid web3 name: {reason}"),
+            serde_json::json!({ "name": name, "valid": false, "reason": reason, "available": false }),
+        );
+        return Ok(());
+    }
+
+    let storage = storage_at(matches, &cli).await?;
+    let banned = storage
+        .fetch(
+            &kilt::storage()
+                .web3_names()
+                .banned(AsciiWeb3Name(BoundedVec(name.as_bytes().to_vec()))),
+        )
+        .await?
+        .is_some();
+    let owner = web3_name_owner(&storage, name)
+        .await?
+        .map(|did| did_uri(&did));
+
+    let text = match (&owner, banned) {
+        (Some(owner), _) => format!("{name} is taken by {owner}"),
+        (None, true) => format!("{name} is banned"),
+        (None, false) => format!("{name} is available"),
+    };
+    print_output(
+        matches,
+        text,
+        serde_json::json!({
+            "name": name,
+            "valid": true,
+            "banned": banned,
+            "owner": owner,
+            "available": !banned && owner.is_none(),
+        }),
+    );
+    Ok(())
+}
+
+// The runtime only accepts lowercase ASCII letters, digits, `-` and `_`.
+fn validate(name: &str, min: u32, max: u32) -> Result<(), String> {
+    if name.len() < min as usize {
+        return Err(format!("shorter than {min} characters"));
+    }
+    if name.len() > max as usize {
+        return Err(format!("longer than {max} characters"));
+    }
+    match name
+        .chars()
+        .find(|c| !matches!(c, 'a'..='z' | '0'..='9' | '-' | '_'))
+    {
+        Some(c) => Err(format!("character {c:?} is not allowed")),
+        None => Ok(()),
+    }
+}
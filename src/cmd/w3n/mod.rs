@@ -0,0 +1,15 @@
+mod check;
+
+pub fn command() -> clap::Command {
+    clap::Command::new("w3n")
+        .about("Web3Name commands")
+        .subcommand_required(true)
+        .subcommands([check::command()])
+}
+
+pub async fn run(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("check", matches)) => check::run(matches).await,
+        _ => Ok(()),
+    }
+}
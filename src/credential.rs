@@ -5,6 +5,7 @@ use rand::RngCore;
 
 use blake2::{digest::consts::U32, Blake2b, Digest};
 type Blake2b256 = Blake2b<U32>;
+type ClaimHash = (String, Vec<u8>);
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct Credential {
@@ -61,6 +62,32 @@ pub struct Proof {
     pub reveal_proof: Vec<String>,
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct Presentation {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    #[serde(rename = "type")]
+    pub type_: Vec<String>,
+    pub holder: String,
+    #[serde(rename = "verifiableCredential")]
+    pub verifiable_credential: Vec<Credential>,
+    pub proof: PresentationProof,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct PresentationProof {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub created: String,
+    pub challenge: String,
+    #[serde(rename = "proofPurpose")]
+    pub proof_purpose: String,
+    #[serde(rename = "verificationMethod")]
+    pub verification_method: String,
+    #[serde(rename = "proofValue")]
+    pub proof_value: String,
+}
+
 pub struct CredentialBuilder {
     context: Vec<String>,
     type_: Vec<String>,
@@ -156,43 +183,16 @@ impl CredentialBuilder {
             reveal_proof: vec![],
         };
 
-        let mut credential_subject = self
+        let credential_subject = self
             .credential_subject
-            .clone()
+            .as_ref()
             .expect("credential subject is required");
 
-        // respect @vocab
-        if let Some(context) = credential_subject.get("@context") {
-            if let Some(ctx_obj) = context.as_object() {
-                if let Some(vocab) = ctx_obj.get("@vocab") {
-                    if let Some(vocab_str) = vocab.as_str() {
-                        let mut new_credential_subject = serde_json::Map::new();
-                        credential_subject.iter().for_each(|(key, value)| {
-                            if key != "@context" {
-                                let mut key = key.to_owned();
-                                if !key.starts_with('@') {
-                                    key = format!("{vocab_str}{key}");
-                                }
-                                new_credential_subject.insert(key, value.clone());
-                            }
-                        });
-                        credential_subject = new_credential_subject;
-                    }
-                }
-            }
-        }
-
-        // compute prehashes for all claims
-        let mut pre_hashes = vec![];
-        for (key, value) in credential_subject.iter() {
-            let obj = serde_json::to_vec(&json!({ key: value }))?;
-            let mut hasher = Blake2b256::new();
-            hasher.update(&obj);
-            let hash = hasher.finalize().to_vec();
-            pre_hashes.push(hash);
-        }
-
-        // sort numerically
+        // compute prehashes for all claims and sort them numerically
+        let mut pre_hashes = claim_hashes(credential_subject)?
+            .into_iter()
+            .map(|(_, hash)| hash)
+            .collect::<Vec<_>>();
         pre_hashes.sort();
 
         // collect data for the root hash on the fly
@@ -267,50 +267,54 @@ impl CredentialBuilder {
 }
 
 impl Credential {
-    pub fn verify(&self) -> Result<(), Box<dyn std::error::Error>> {
-        if self.proof.type_ != "KiltAttestationProofV1" {
-            return Err("invalid proof type".into());
+    // Derive a presentation that hides the given claims. The salts of hidden claims are dropped
+    // but all commitments are kept, so the derived credential still verifies against the same id.
+    pub fn derive(&self, hide: &[&str]) -> Result<Credential, Box<dyn std::error::Error>> {
+        for name in hide {
+            if *name == "@context" || !self.credential_subject.contains_key(*name) {
+                return Err(format!("unknown claim {name}").into());
+            }
         }
 
-        let mut credential_subject = self.credential_subject.clone();
-
-        // respect @vocab
-        if let Some(context) = credential_subject.get("@context") {
-            if let Some(ctx_obj) = context.as_object() {
-                if let Some(vocab) = ctx_obj.get("@vocab") {
-                    if let Some(vocab_str) = vocab.as_str() {
-                        let mut new_credential_subject = serde_json::Map::new();
-                        credential_subject.iter().for_each(|(key, value)| {
-                            if key != "@context" {
-                                let mut key = key.to_owned();
-                                if !key.starts_with('@') {
-                                    key = format!("{vocab_str}{key}");
-                                }
-                                new_credential_subject.insert(key, value.clone());
-                            }
-                        });
-                        credential_subject = new_credential_subject;
-                    }
-                }
-            }
+        let mut claims = claim_hashes(&self.credential_subject)?;
+        claims.sort_by(|a, b| a.1.cmp(&b.1));
+        if claims.len() != self.proof.reveal_proof.len() {
+            return Err("reveal proof length does not match number of claims".into());
         }
 
-        // compute prehashes for all claims
-        let mut pre_hashes = vec![];
-        for (key, value) in credential_subject.iter() {
-            let obj = serde_json::to_vec(&json!({ key: value }))?;
-            let mut hasher = Blake2b256::new();
-            hasher.update(&obj);
-            let hash = hasher.finalize().to_vec();
-            pre_hashes.push(hash);
+        let mut derived = self.clone();
+        derived.proof.reveal_proof = claims
+            .iter()
+            .zip(&self.proof.reveal_proof)
+            .filter(|((key, _), _)| !hide.contains(&key.as_str()))
+            .map(|(_, salt)| salt.clone())
+            .collect();
+        for name in hide {
+            derived.credential_subject.remove(*name);
         }
 
-        // sort numerically
+        Ok(derived)
+    }
+
+    pub fn verify(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.proof.type_ != "KiltAttestationProofV1" {
+            return Err("invalid proof type".into());
+        }
+
+        // compute prehashes for all revealed claims and sort them numerically
+        let mut pre_hashes = claim_hashes(&self.credential_subject)?
+            .into_iter()
+            .map(|(_, hash)| hash)
+            .collect::<Vec<_>>();
         pre_hashes.sort();
 
+        // hidden claims have no salt, but their commitments stay in the proof
         if pre_hashes.len() != self.proof.reveal_proof.len() {
             return Err("reveal proof length does not match number of claims".into());
         }
+        if pre_hashes.len() > self.proof.commitments.len() {
+            return Err("more claims revealed than committed to".into());
+        }
 
         for (i, pre_hash) in pre_hashes.iter().enumerate() {
             // hash salt + 0x3078 + pre_hash
@@ -350,6 +354,86 @@ impl Credential {
     }
 }
 
+impl Presentation {
+    // Create an unsigned presentation; `proof_value` is filled in once the payload is signed.
+    pub fn new(
+        holder: &str,
+        verifiable_credential: Vec<Credential>,
+        challenge: &str,
+        verification_method: &str,
+        created: &str,
+    ) -> Self {
+        Self {
+            context: vec!["https://www.w3.org/2018/credentials/v1".to_string()],
+            type_: vec!["VerifiablePresentation".to_string()],
+            holder: holder.to_string(),
+            verifiable_credential,
+            proof: PresentationProof {
+                type_: "KiltDidSignatureV1".to_string(),
+                created: created.to_string(),
+                challenge: challenge.to_string(),
+                proof_purpose: "authentication".to_string(),
+                verification_method: verification_method.to_string(),
+                proof_value: String::new(),
+            },
+        }
+    }
+
+    // The bytes the holder signs: the blake2b-256 hash of everything except the signature.
+    pub fn signing_payload(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let payload = serde_json::to_vec(&json!({
+            "holder": self.holder,
+            "verifiableCredential": self.verifiable_credential,
+            "challenge": self.proof.challenge,
+            "created": self.proof.created,
+            "verificationMethod": self.proof.verification_method,
+        }))?;
+        let mut hasher = Blake2b256::new();
+        hasher.update(&payload);
+        Ok(hasher.finalize().to_vec())
+    }
+
+    pub fn set_signature(&mut self, signature: &[u8]) {
+        self.proof.proof_value = format!("z{}", signature.to_base58());
+    }
+
+    pub fn signature(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        Ok(self
+            .proof
+            .proof_value
+            .strip_prefix('z')
+            .ok_or("proof value is not base58 multibase encoded")?
+            .from_base58()
+            .map_err(|_| "failed to parse proof value")?)
+    }
+}
+
+// Compute the prehash of every claim, keyed by the claim name as it appears in the credential
+// subject. Claim names are expanded with the @vocab of the subject's @context if there is one.
+fn claim_hashes(
+    credential_subject: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<ClaimHash>, Box<dyn std::error::Error>> {
+    let vocab = credential_subject
+        .get("@context")
+        .and_then(|context| context.as_object())
+        .and_then(|context| context.get("@vocab"))
+        .and_then(|vocab| vocab.as_str());
+
+    let mut hashes = vec![];
+    for (key, value) in credential_subject.iter() {
+        let expanded = match vocab {
+            Some(_) if key == "@context" => continue,
+            Some(vocab) if !key.starts_with('@') => format!("{vocab}{key}"),
+            _ => key.to_owned(),
+        };
+        let obj = serde_json::to_vec(&json!({ expanded: value }))?;
+        let mut hasher = Blake2b256::new();
+        hasher.update(&obj);
+        hashes.push((key.to_owned(), hasher.finalize().to_vec()));
+    }
+    Ok(hashes)
+}
+
 mod test {
 
     #[test]
@@ -383,4 +467,45 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn it_derives() -> Result<(), Box<dyn std::error::Error>> {
+        use super::*;
+
+        let cred = CredentialBuilder::new()
+            .with_ctype(
+                "kilt:ctype:0x0586412d7b8adf811c288211c9c704b3331bb3adb61fba6448c89453568180f6",
+            )
+            .with_credential_subject(
+                json!({
+                    "@id": "kilt:did:123456789",
+                    "name": "Alice",
+                    "age": 42,
+                    "email": "alice@example.com",
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            )
+            .create_proof("123456789")?
+            .build()?;
+
+        let derived = cred.derive(&["email", "age"])?;
+        assert!(!derived.credential_subject.contains_key("email"));
+        assert!(!derived.credential_subject.contains_key("age"));
+        assert_eq!(derived.proof.reveal_proof.len(), 2);
+        assert_eq!(derived.proof.commitments, cred.proof.commitments);
+        derived.verify().expect("verify failed");
+
+        // tampering with a revealed claim must be detected
+        let mut tampered = derived.clone();
+        tampered
+            .credential_subject
+            .insert("name".to_string(), json!("Mallory"));
+        assert!(tampered.verify().is_err());
+
+        assert!(cred.derive(&["unknown"]).is_err());
+
+        Ok(())
+    }
 }
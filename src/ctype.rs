@@ -0,0 +1,788 @@
+use std::collections::HashMap;
+
+use crate::kilt::{
+    self,
+    runtime_types::{
+        ctype, did, pallet_proxy::pallet as proxy, pallet_utility::pallet as utility,
+        spiritnet_runtime::RuntimeCall,
+    },
+    KiltConfig,
+};
+use crate::Storage;
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use codec::Decode;
+use serde_json::{Map, Value};
+use subxt::{ext::sp_core::H256, OnlineClient};
+
+type Blake2b256 = Blake2b<U32>;
+
+pub const DRAFT_01_METASCHEMA: &str = "http://kilt-protocol.org/draft-01/ctype#";
+pub const V1_METASCHEMA: &str =
+    "ipfs://bafybeiah66wbkhqbqn7idkostj2iqyan2tstc4tpqt65udlhimd7hcxjyq/";
+
+// CTypes that can be referenced via `$ref`, keyed by their `kilt:ctype:0x...` id.
+pub type CTypeRefs = HashMap<String, CType>;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct CType {
+    #[serde(rename = "$id", alias = "@id")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    #[serde(rename = "$schema")]
+    pub schema: String,
+
+    #[serde(rename = "additionalProperties")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<bool>,
+
+    pub properties: serde_json::Map<String, serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+
+    pub title: String,
+
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+impl CType {
+    pub fn new(title: String, properties: serde_json::Map<String, serde_json::Value>) -> Self {
+        Self {
+            id: None,
+            title,
+            type_: "object".to_string(),
+            schema: DRAFT_01_METASCHEMA.to_string(),
+            additional_properties: None,
+            properties,
+            required: None,
+        }
+    }
+
+    // Create a CType using the v1 metaschema, which forbids claims not defined in `properties`.
+    pub fn new_v1(title: String, properties: serde_json::Map<String, serde_json::Value>) -> Self {
+        Self {
+            schema: V1_METASCHEMA.to_string(),
+            additional_properties: Some(false),
+            ..Self::new(title, properties)
+        }
+    }
+
+    // The representation that is stored on chain and hashed: canonical JSON without the id.
+    pub fn serialize(&self) -> Result<String, Box<dyn std::error::Error>> {
+        if self.schema != DRAFT_01_METASCHEMA && self.schema != V1_METASCHEMA {
+            return Err(format!("unsupported CType metaschema {}", self.schema).into());
+        }
+        let mut copy = self.clone();
+        copy.id = None;
+        Ok(canonical_json(&serde_json::to_value(&copy)?))
+    }
+
+    pub fn hash(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let mut hasher = Blake2b256::new();
+        hasher.update(self.serialize()?);
+        Ok(format!("0x{}", hex::encode(hasher.finalize())))
+    }
+
+    pub fn id(&self) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(format!("kilt:ctype:{}", self.hash()?))
+    }
+
+    // Ids of the CTypes referenced via `$ref` from this CType's properties.
+    pub fn references(&self) -> Vec<String> {
+        let mut refs = vec![];
+        for schema in self.properties.values() {
+            collect_references(schema, &mut refs);
+        }
+        refs
+    }
+
+    // Check a claims map against this CType. `refs` must contain all (transitively) referenced CTypes.
+    pub fn validate(
+        &self,
+        claims: &Map<String, Value>,
+        refs: &CTypeRefs,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        validate_ctype(self, claims, refs, "")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CTypeBuilder {
+    title: String,
+    v1: bool,
+    properties: Map<String, Value>,
+    required: Vec<String>,
+}
+
+impl CTypeBuilder {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            v1: false,
+            properties: Map::new(),
+            required: vec![],
+        }
+    }
+
+    pub fn with_v1_metaschema(mut self) -> Self {
+        self.v1 = true;
+        self
+    }
+
+    pub fn with_property(mut self, name: &str, property: Property) -> Self {
+        self.properties.insert(name.to_string(), property.0);
+        self
+    }
+
+    pub fn with_required_property(mut self, name: &str, property: Property) -> Self {
+        self.required.push(name.to_string());
+        self.with_property(name, property)
+    }
+
+    pub fn build(self) -> CType {
+        let mut ctype = if self.v1 {
+            CType::new_v1(self.title, self.properties)
+        } else {
+            CType::new(self.title, self.properties)
+        };
+        if !self.required.is_empty() {
+            ctype.required = Some(self.required);
+        }
+        ctype
+    }
+}
+
+// The JSON schema of a single CType property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property(pub Value);
+
+impl Property {
+    fn typed(type_: &str) -> Self {
+        Self(serde_json::json!({ "type": type_ }))
+    }
+
+    pub fn string() -> Self {
+        Self::typed("string")
+    }
+
+    pub fn integer() -> Self {
+        Self::typed("integer")
+    }
+
+    pub fn number() -> Self {
+        Self::typed("number")
+    }
+
+    pub fn boolean() -> Self {
+        Self::typed("boolean")
+    }
+
+    pub fn array(items: Property) -> Self {
+        Self(serde_json::json!({ "type": "array", "items": items.0 }))
+    }
+
+    pub fn object(properties: Vec<(&str, Property)>) -> Self {
+        let properties = properties
+            .into_iter()
+            .map(|(name, property)| (name.to_string(), property.0))
+            .collect::<Map<_, _>>();
+        Self(serde_json::json!({ "type": "object", "properties": properties }))
+    }
+
+    // Reference a whole CType by its id (`kilt:ctype:0x...`).
+    pub fn reference(ctype_id: &str) -> Self {
+        Self(serde_json::json!({ "$ref": ctype_id }))
+    }
+
+    // Reference a single property of another CType.
+    pub fn reference_property(ctype_id: &str, name: &str) -> Self {
+        Self(serde_json::json!({ "$ref": format!("{ctype_id}#/properties/{name}") }))
+    }
+
+    pub fn with_format(mut self, format: &str) -> Self {
+        self.0["format"] = format.into();
+        self
+    }
+
+    pub fn with_enum(mut self, values: Vec<Value>) -> Self {
+        self.0["enum"] = values.into();
+        self
+    }
+}
+
+// Serialize JSON like the KILT SDK does for hashing (`JSON.stringify(jsonabc.sortObj(value))`):
+// object keys are sorted case-insensitively (integer keys first, as in JavaScript objects), arrays
+// are sorted by their serialized elements and numbers are printed in JavaScript notation.
+pub fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map
+                .iter()
+                .map(|(key, value)| (key, canonical_json(value)))
+                .collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| js_key_order(a, b));
+            let entries = entries
+                .into_iter()
+                .map(|(key, value)| format!("{}:{value}", Value::from(key.as_str())))
+                .collect::<Vec<_>>();
+            format!("{{{}}}", entries.join(","))
+        }
+        Value::Array(values) => {
+            let mut values = values.iter().map(canonical_json).collect::<Vec<_>>();
+            values.sort_by(|a, b| a.encode_utf16().cmp(b.encode_utf16()));
+            format!("[{}]", values.join(","))
+        }
+        Value::Number(n) if n.is_f64() => js_number(n.as_f64().unwrap_or_default()),
+        _ => value.to_string(),
+    }
+}
+
+fn js_key_order(a: &str, b: &str) -> std::cmp::Ordering {
+    // JavaScript objects always enumerate array index keys first, in numeric order
+    let index = |key: &str| {
+        key.parse::<u32>()
+            .ok()
+            .filter(|i| *i < u32::MAX && i.to_string() == key)
+    };
+    match (index(a), index(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a
+            .to_lowercase()
+            .encode_utf16()
+            .cmp(b.to_lowercase().encode_utf16())
+            .then_with(|| a.encode_utf16().cmp(b.encode_utf16())),
+    }
+}
+
+// Format a number like JavaScript's `Number.prototype.toString`.
+fn js_number(n: f64) -> String {
+    if n == 0.0 {
+        return "0".to_string();
+    }
+    // shortest round-trip digits and the decimal exponent
+    let sci = format!("{:e}", n.abs());
+    let (mantissa, exp) = sci.split_once('e').unwrap_or((&sci, "0"));
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let e = exp.parse::<i32>().unwrap_or_default() + 1;
+    let formatted = if k <= e && e <= 21 {
+        format!("{digits}{}", "0".repeat((e - k) as usize))
+    } else if 0 < e && e <= 21 {
+        format!("{}.{}", &digits[..e as usize], &digits[e as usize..])
+    } else if -6 < e && e <= 0 {
+        format!("0.{}{digits}", "0".repeat(-e as usize))
+    } else {
+        let mantissa = if k == 1 {
+            digits.clone()
+        } else {
+            format!("{}.{}", &digits[..1], &digits[1..])
+        };
+        format!(
+            "{mantissa}e{}{}",
+            if e > 0 { '+' } else { '-' },
+            (e - 1).abs()
+        )
+    };
+    if n < 0.0 {
+        format!("-{formatted}")
+    } else {
+        formatted
+    }
+}
+
+fn collect_references(schema: &Value, refs: &mut Vec<String>) {
+    match schema {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                let id = reference.split('#').next().unwrap_or_default().to_string();
+                if !refs.contains(&id) {
+                    refs.push(id);
+                }
+            }
+            map.values().for_each(|v| collect_references(v, refs));
+        }
+        Value::Array(values) => values.iter().for_each(|v| collect_references(v, refs)),
+        _ => {}
+    }
+}
+
+fn property_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+fn validate_ctype(
+    ctype: &CType,
+    claims: &Map<String, Value>,
+    refs: &CTypeRefs,
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    validate_object(
+        &ctype.properties,
+        ctype.additional_properties,
+        ctype.required.as_deref().unwrap_or_default(),
+        claims,
+        refs,
+        path,
+    )
+}
+
+fn validate_object(
+    properties: &Map<String, Value>,
+    additional_properties: Option<bool>,
+    required: &[String],
+    claims: &Map<String, Value>,
+    refs: &CTypeRefs,
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (name, value) in claims {
+        if name.starts_with('@') {
+            continue;
+        }
+        let path = property_path(path, name);
+        match properties.get(name) {
+            Some(schema) => validate_value(schema, value, refs, &path)?,
+            None if additional_properties != Some(false) => {}
+            None => return Err(format!("property {path}: not defined in the CType").into()),
+        }
+    }
+    for name in required {
+        if !claims.contains_key(name) {
+            return Err(format!("property {}: required", property_path(path, name)).into());
+        }
+    }
+    Ok(())
+}
+
+fn validate_value(
+    schema: &Value,
+    value: &Value,
+    refs: &CTypeRefs,
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let (id, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+        let ctype = refs
+            .get(id)
+            .ok_or_else(|| format!("property {path}: unknown CType {id}"))?;
+        if pointer.is_empty() || pointer == "/" {
+            let claims = value
+                .as_object()
+                .ok_or_else(|| format!("property {path}: expected object, got {value}"))?;
+            return validate_ctype(ctype, claims, refs, path);
+        }
+        let schema = pointer
+            .strip_prefix("/properties/")
+            .and_then(|name| ctype.properties.get(name))
+            .ok_or_else(|| format!("property {path}: cannot resolve {reference}"))?;
+        return validate_value(schema, value, refs, path);
+    }
+
+    if let Some(type_) = schema.get("type").and_then(Value::as_str) {
+        let valid = match type_ {
+            "string" => value.is_string(),
+            "integer" => value.as_f64().map(|n| n.fract() == 0.0).unwrap_or(false),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => return Err(format!("property {path}: unsupported type {type_}").into()),
+        };
+        if !valid {
+            return Err(format!("property {path}: expected {type_}, got {value}").into());
+        }
+    }
+
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        if !values.contains(value) {
+            return Err(
+                format!("property {path}: {value} is not one of the allowed values").into(),
+            );
+        }
+    }
+
+    if let (Some(format), Some(value)) =
+        (schema.get("format").and_then(Value::as_str), value.as_str())
+    {
+        if !valid_format(format, value) {
+            return Err(format!("property {path}: {value:?} is not a valid {format}").into());
+        }
+    }
+
+    if let (Some(properties), Some(claims)) = (
+        schema.get("properties").and_then(Value::as_object),
+        value.as_object(),
+    ) {
+        let required = schema
+            .get("required")
+            .and_then(|required| serde_json::from_value::<Vec<String>>(required.clone()).ok())
+            .unwrap_or_default();
+        let additional_properties = schema.get("additionalProperties").and_then(Value::as_bool);
+        validate_object(
+            properties,
+            additional_properties,
+            &required,
+            claims,
+            refs,
+            path,
+        )?;
+    }
+
+    if let (Some(items), Some(values)) = (schema.get("items"), value.as_array()) {
+        for (i, item) in values.iter().enumerate() {
+            validate_value(items, item, refs, &format!("{path}[{i}]"))?;
+        }
+    }
+    Ok(())
+}
+
+// Unknown formats are only annotations in JSON schema and always pass.
+fn valid_format(format: &str, value: &str) -> bool {
+    match format {
+        "date" => chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        "time" => chrono::NaiveTime::parse_from_str(value, "%H:%M:%S%.f").is_ok(),
+        "date-time" => chrono::DateTime::parse_from_rfc3339(value).is_ok(),
+        "uri" => value
+            .split_once(':')
+            .map(|(scheme, _)| {
+                scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            })
+            .unwrap_or(false),
+        _ => true,
+    }
+}
+
+// Collect the CType definitions added by a call, looking into DID, batch and proxy wrappers.
+fn added_ctypes(call: RuntimeCall, definitions: &mut Vec<Vec<u8>>) {
+    match call {
+        RuntimeCall::Ctype(ctype::pallet::Call::add { ctype }) => definitions.push(ctype),
+        RuntimeCall::Did(did::pallet::Call::submit_did_call { did_call, .. }) => {
+            added_ctypes(did_call.call, definitions)
+        }
+        RuntimeCall::Utility(
+            utility::Call::batch { calls }
+            | utility::Call::batch_all { calls }
+            | utility::Call::force_batch { calls },
+        ) => calls
+            .into_iter()
+            .for_each(|call| added_ctypes(call, definitions)),
+        RuntimeCall::Proxy(
+            proxy::Call::proxy { call, .. } | proxy::Call::proxy_announced { call, .. },
+        ) => added_ctypes(*call, definitions),
+        _ => {}
+    }
+}
+
+// Fetch a CType definition by its hash: the storage entry only records the block in which the
+// CType was created, so the definition is taken from the `Ctype.add` call in that block.
+pub async fn fetch_json(
+    cli: &OnlineClient<KiltConfig>,
+    storage: &Storage,
+    hash: H256,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let entry = storage
+        .fetch(&kilt::storage().ctype().ctypes(hash))
+        .await?
+        .ok_or_else(|| format!("ctype {hash:?} not found"))?;
+    let block_hash = cli
+        .rpc()
+        .block_hash(Some(entry.created_at.into()))
+        .await?
+        .ok_or_else(|| format!("block {} not found", entry.created_at))?;
+    let body = cli.blocks().at(block_hash).await?.body().await?;
+
+    let mut definitions = vec![];
+    for ext in body.extrinsics().iter() {
+        // extrinsics of other pallets may not decode with our runtime types, skip them
+        if let Ok(call) = RuntimeCall::decode(&mut ext?.call_bytes()) {
+            added_ctypes(call, &mut definitions);
+        }
+    }
+    let definition = definitions
+        .into_iter()
+        .find(|definition| subxt::ext::sp_core::blake2_256(definition) == hash.0)
+        .ok_or_else(|| format!("ctype {hash:?} not found in block {}", entry.created_at))?;
+    Ok(serde_json::from_slice(&definition)?)
+}
+
+pub async fn fetch(
+    cli: &OnlineClient<KiltConfig>,
+    storage: &Storage,
+    hash: H256,
+) -> Result<CType, Box<dyn std::error::Error>> {
+    Ok(serde_json::from_value(
+        fetch_json(cli, storage, hash).await?,
+    )?)
+}
+
+mod test {
+    #[test]
+    fn test_ctype() {
+        use super::*;
+
+        let ctype = CType::new(
+            "test".to_string(),
+            serde_json::json!({
+                "test": {
+                    "z": "string",
+                    "a": "string",
+                }
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        );
+        let sorted = ctype.serialize().unwrap();
+        assert_eq!(
+            sorted,
+            r#"{"$schema":"http://kilt-protocol.org/draft-01/ctype#","properties":{"test":{"a":"string","z":"string"}},"title":"test","type":"object"}"#
+        );
+    }
+
+    #[test]
+    fn test_ctype_hash() {
+        use super::*;
+
+        // CTypes used by SocialKYC, hashes as registered on chain by the KILT SDK
+        let vectors = [
+            (
+                r#"{"$schema":"http://kilt-protocol.org/draft-01/ctype#","properties":{"Email":{"type":"string"}},"title":"Email","type":"object"}"#,
+                "0x3291bb126e33b4862d421bfaa1d2f272e6cdfc4f96658988fbcffea8914bd9ac",
+            ),
+            (
+                r#"{"title":"Twitter","type":"object","$schema":"http://kilt-protocol.org/draft-01/ctype#","properties":{"Twitter":{"type":"string"}}}"#,
+                "0x47d04c42bdf7fdd3fc5a194bcaa367b2f4766a6b16ae3df628927656d818f420",
+            ),
+            (
+                r#"{"$id":"kilt:ctype:0xd8c61a235204cb9e3c6acb1898d78880488846a7247d325b833243b46d923abe","$schema":"http://kilt-protocol.org/draft-01/ctype#","properties":{"Username":{"type":"string"},"Discriminator":{"type":"string"},"User ID":{"type":"string"}},"title":"Discord","type":"object"}"#,
+                "0xd8c61a235204cb9e3c6acb1898d78880488846a7247d325b833243b46d923abe",
+            ),
+            (
+                r#"{"$schema":"http://kilt-protocol.org/draft-01/ctype#","properties":{"Username":{"type":"string"},"User ID":{"type":"string"}},"title":"Twitch","type":"object"}"#,
+                "0x568ec5ffd7771c4677a5470771adcdea1ea4d6b566f060dc419ff133a0089d80",
+            ),
+            (
+                r#"{"$schema":"http://kilt-protocol.org/draft-01/ctype#","properties":{"First name":{"type":"string"},"Last name":{"type":"string"},"Username":{"type":"string"},"User ID":{"type":"number"}},"title":"Telegram","type":"object"}"#,
+                "0xcef8f3fe5aa7379faea95327942fd77287e1c144e3f53243e55705f11e890a4c",
+            ),
+            // the domain linkage CType of the well-known DID configuration
+            (
+                r#"{"$schema":"http://kilt-protocol.org/draft-01/ctype#","title":"Domain Linkage Credential","properties":{"id":{"type":"string"},"origin":{"type":"string"}},"type":"object"}"#,
+                "0x9d271c790775ee831352291f01c5d04c7979713a5896dcf5e81708184cc5c643",
+            ),
+        ];
+        for (json, hash) in vectors {
+            let ctype: CType = serde_json::from_str(json).unwrap();
+            assert_eq!(ctype.hash().unwrap(), hash);
+        }
+
+        // v1 CTypes: the SocialKYC email CType and the example from the KILT SDK documentation
+        let vectors = [
+            (
+                r#"{"$schema":"ipfs://bafybeiah66wbkhqbqn7idkostj2iqyan2tstc4tpqt65udlhimd7hcxjyq/","additionalProperties":false,"properties":{"Email":{"type":"string"}},"title":"Email","type":"object"}"#,
+                "0xae5bc64e500eb576b7b137288cec5d532094e103be46872f1ad54641e477d9fe",
+            ),
+            (
+                r#"{"$schema":"ipfs://bafybeiah66wbkhqbqn7idkostj2iqyan2tstc4tpqt65udlhimd7hcxjyq/","title":"Drivers License by did:kilt:4t9FPVbcN42UMxt3Z2Y4Wx38qPL8bLduAB11gLZSwn5hVEfH","properties":{"name":{"type":"string"},"age":{"type":"integer"},"id":{"type":"string"}},"type":"object","additionalProperties":false}"#,
+                "0x4f1d68ac46daf4613181b33b16faaf10cf94879dc2246d7485dc2ccbb843641d",
+            ),
+            // nested objects, mixed-case keys and arrays of objects; no such CType is registered
+            // yet, so these hashes come from `JSON.stringify(jsonabc.sortObj(ctype))` run in
+            // Node.js, which sorts keys case-insensitively and arrays by their serialization
+            (
+                r#"{"$schema":"ipfs://bafybeiah66wbkhqbqn7idkostj2iqyan2tstc4tpqt65udlhimd7hcxjyq/","additionalProperties":false,"properties":{"address":{"type":"object","properties":{"zip":{"type":"integer"},"city":{"type":"string"}},"required":["city"]},"name":{"type":"string"},"contact":{"$ref":"kilt:ctype:0xae5bc64e500eb576b7b137288cec5d532094e103be46872f1ad54641e477d9fe#/properties/Email"},"license":{"$ref":"kilt:ctype:0x4f1d68ac46daf4613181b33b16faaf10cf94879dc2246d7485dc2ccbb843641d"},"languages":{"type":"array","items":{"type":"string","enum":["de","en"]}}},"title":"Profile","type":"object"}"#,
+                "0xd9a15ba5df7910181b2b045d1338da3dd79ec8126a489872fef722feaff1ee9a",
+            ),
+            (
+                r#"{"$schema":"ipfs://bafybeiah66wbkhqbqn7idkostj2iqyan2tstc4tpqt65udlhimd7hcxjyq/","additionalProperties":false,"properties":{"Name":{"type":"string"},"age":{"type":"integer","minimum":0},"Address":{"type":"object","properties":{"zip":{"type":"string"},"Street":{"type":"string"},"city":{"type":"string"}},"required":["city","Street"]}},"title":"Person","type":"object"}"#,
+                "0xfd1156bef47f333bf5317d8f56de36d8d856c35b3aa2feb1fca9829bd76d0092",
+            ),
+            (
+                r#"{"$schema":"ipfs://bafybeiah66wbkhqbqn7idkostj2iqyan2tstc4tpqt65udlhimd7hcxjyq/","additionalProperties":false,"properties":{"contact":{"anyOf":[{"type":"integer"},{"type":"string","format":"email"},{"$ref":"kilt:ctype:0x3291bb126e33b4862d421bfaa1d2f272e6cdfc4f96658988fbcffea8914bd9ac#/properties/Email"}]},"languages":{"type":"array","items":{"type":"string","enum":["en","de","Fr"]}}},"title":"Contact","type":"object"}"#,
+                "0x6dbdac6efd5ef21ef70de6392846b4c1890645d10fc9b012f079c47ca0040331",
+            ),
+        ];
+        for (json, hash) in vectors {
+            let ctype: CType = serde_json::from_str(json).unwrap();
+            assert_eq!(ctype.hash().unwrap(), hash);
+        }
+
+        let ctype = CType::new_v1(
+            "Email".to_string(),
+            serde_json::json!({ "Email": { "type": "string" } })
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        assert_eq!(
+            ctype.serialize().unwrap(),
+            r#"{"$schema":"ipfs://bafybeiah66wbkhqbqn7idkostj2iqyan2tstc4tpqt65udlhimd7hcxjyq/","additionalProperties":false,"properties":{"Email":{"type":"string"}},"title":"Email","type":"object"}"#
+        );
+
+        let mut ctype = ctype;
+        ctype.schema = "http://json-schema.org/draft-07/schema#".to_string();
+        assert!(ctype.serialize().is_err());
+    }
+
+    #[test]
+    fn test_canonical_json() {
+        use super::*;
+
+        let value = serde_json::json!({
+            "b": [3, 1.5, "x", { "z": 1, "A": 2 }],
+            "a": { "2": true, "10": false, "B": null, "a": "\u{1}\"" },
+            "C": [1e21, 1e-7, 0.000001, -2.50, 100.0],
+        });
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":{"2":true,"10":false,"a":"\u0001\"","B":null},"b":["x",1.5,3,{"A":2,"z":1}],"C":[-2.5,0.000001,100,1e+21,1e-7]}"#
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        use super::*;
+
+        let address: CType = serde_json::from_value(serde_json::json!({
+            "$schema": "http://kilt-protocol.org/draft-01/ctype#",
+            "properties": {
+                "city": { "type": "string" },
+                "zip": { "type": "integer" },
+            },
+            "required": ["city"],
+            "title": "address",
+            "type": "object",
+        }))
+        .unwrap();
+        let address_id = address.id().unwrap();
+        let mut ctype: CType = serde_json::from_value(serde_json::json!({
+            "$schema": "http://kilt-protocol.org/draft-01/ctype#",
+            "properties": {
+                "name": { "type": "string" },
+                "born": { "type": "string", "format": "date" },
+                "level": { "type": "string", "enum": ["gold", "silver"] },
+                "home": { "$ref": address_id },
+                "zip": { "$ref": format!("{address_id}#/properties/zip") },
+            },
+            "title": "person",
+            "type": "object",
+        }))
+        .unwrap();
+        assert_eq!(ctype.references(), vec![address_id.clone()]);
+        let refs = CTypeRefs::from([(address_id, address)]);
+
+        let check = |claims: serde_json::Value, ctype: &CType| {
+            ctype
+                .validate(claims.as_object().unwrap(), &refs)
+                .map_err(|e| e.to_string())
+        };
+        let valid = serde_json::json!({
+            "@id": "did:kilt:4abc",
+            "name": "Alice",
+            "born": "1990-01-31",
+            "level": "gold",
+            "home": { "city": "Berlin", "zip": 10115 },
+            "zip": 10115,
+        });
+        assert!(check(valid, &ctype).is_ok());
+        assert_eq!(
+            check(serde_json::json!({ "name": 1 }), &ctype).unwrap_err(),
+            "property name: expected string, got 1"
+        );
+        assert!(check(serde_json::json!({ "born": "1990-13-01" }), &ctype).is_err());
+        assert!(check(serde_json::json!({ "level": "bronze" }), &ctype).is_err());
+        assert!(check(serde_json::json!({ "zip": "10115" }), &ctype).is_err());
+        assert!(check(serde_json::json!({ "unknown": true }), &ctype).is_ok());
+        assert_eq!(
+            check(serde_json::json!({ "home": { "zip": 1 } }), &ctype).unwrap_err(),
+            "property home.city: required"
+        );
+
+        ctype.additional_properties = Some(false);
+        assert_eq!(
+            check(serde_json::json!({ "unknown": true }), &ctype).unwrap_err(),
+            "property unknown: not defined in the CType"
+        );
+
+        ctype.required = Some(vec!["name".to_string()]);
+        assert_eq!(
+            check(serde_json::json!({}), &ctype).unwrap_err(),
+            "property name: required"
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        use super::*;
+
+        let address = CTypeBuilder::new("address")
+            .with_required_property("city", Property::string())
+            .with_property("zip", Property::integer())
+            .build();
+        let address_id = address.id().unwrap();
+        assert!(address_id.starts_with("kilt:ctype:0x"));
+        assert_eq!(address_id.len(), "kilt:ctype:0x".len() + 64);
+
+        let ctype = CTypeBuilder::new("person")
+            .with_v1_metaschema()
+            .with_property(
+                "name",
+                Property::object(vec![
+                    ("first", Property::string()),
+                    ("last", Property::string()),
+                ]),
+            )
+            .with_property(
+                "emails",
+                Property::array(Property::string().with_format("uri")),
+            )
+            .with_property("home", Property::reference(&address_id))
+            .with_property(
+                "level",
+                Property::string().with_enum(vec!["gold".into(), "silver".into()]),
+            )
+            .build();
+        assert_eq!(ctype.schema, V1_METASCHEMA);
+        assert_eq!(ctype.references(), vec![address_id.clone()]);
+        assert_eq!(
+            ctype.properties["emails"],
+            serde_json::json!({ "type": "array", "items": { "type": "string", "format": "uri" } })
+        );
+
+        let refs = CTypeRefs::from([(address_id, address)]);
+        let check = |claims: serde_json::Value| {
+            ctype
+                .validate(claims.as_object().unwrap(), &refs)
+                .map_err(|e| e.to_string())
+        };
+        assert!(check(serde_json::json!({
+            "name": { "first": "Alice", "last": "Doe" },
+            "emails": ["mailto:alice@example.com"],
+            "home": { "city": "Berlin" },
+            "level": "silver",
+        }))
+        .is_ok());
+        assert_eq!(
+            check(serde_json::json!({ "name": { "first": 1 } })).unwrap_err(),
+            "property name.first: expected string, got 1"
+        );
+        assert_eq!(
+            check(serde_json::json!({ "emails": ["alice"] })).unwrap_err(),
+            "property emails[0]: \"alice\" is not a valid uri"
+        );
+        assert_eq!(
+            check(serde_json::json!({ "home": { "zip": 1 } })).unwrap_err(),
+            "property home.city: required"
+        );
+    }
+}
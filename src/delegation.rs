@@ -0,0 +1,92 @@
+use subxt::ext::sp_core::{hashing::blake2_256, H256};
+
+use crate::kilt::runtime_types::{
+    delegation::access_control::DelegationAc, runtime_common::authorization::PalletAuthorize,
+    spiritnet_runtime::Runtime,
+};
+
+pub const ATTEST: u32 = 0b01;
+pub const DELEGATE: u32 = 0b10;
+
+pub type Authorization = PalletAuthorize<DelegationAc<Runtime>>;
+
+// Authorize an attestation or public credential operation with a delegation node.
+pub fn authorization(node: H256, max_checks: u32) -> Authorization {
+    PalletAuthorize::Delegation(DelegationAc {
+        subject_node_id: node,
+        max_checks,
+        __subxt_unused_type_params: std::marker::PhantomData,
+    })
+}
+
+// The hash a delegate signs to accept a new delegation node.
+pub fn creation_hash(id: &H256, hierarchy: &H256, parent: &H256, permissions: u32) -> H256 {
+    H256(blake2_256(
+        &[
+            id.as_bytes(),
+            hierarchy.as_bytes(),
+            parent.as_bytes(),
+            &permissions.to_le_bytes(),
+        ]
+        .concat(),
+    ))
+}
+
+pub fn permission_names(permissions: u32) -> Vec<&'static str> {
+    [(ATTEST, "attest"), (DELEGATE, "delegate")]
+        .into_iter()
+        .filter(|(bit, _)| permissions & bit != 0)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+mod test {
+    #[test]
+    fn test_creation_hash() {
+        use super::*;
+
+        // blake2-256 over id ‖ hierarchy ‖ parent ‖ permissions (u32 little endian)
+        let hash = creation_hash(
+            &H256([1; 32]),
+            &H256([2; 32]),
+            &H256([3; 32]),
+            ATTEST | DELEGATE,
+        );
+        assert_eq!(
+            format!("{hash:?}"),
+            "0x27abeb6c670add277e182ca020b923815b03c299ed1664a27562b818041a9f5c"
+        );
+        // a direct child of the root has the hierarchy id as parent
+        let hash = creation_hash(&H256([1; 32]), &H256([2; 32]), &H256([2; 32]), ATTEST);
+        assert_eq!(
+            format!("{hash:?}"),
+            "0x4517ffbe13fbb6505c728f01bcd0ff70d8a86a73e2e90a77d9371ca3cafe9a65"
+        );
+        assert_eq!(permission_names(ATTEST | DELEGATE), ["attest", "delegate"]);
+    }
+
+    #[test]
+    fn test_delegate_signature() {
+        use super::*;
+        use crate::did::{parse_signature, sign, signature_string, verify_signature};
+        use crate::kilt::runtime_types::did::did_details::DidSignature;
+        use subxt::ext::sp_core::{ed25519, Pair};
+
+        let hash = H256([7; 32]);
+        let signature = signature_string(&sign("ed25519", "//Alice", hash.as_bytes()).unwrap());
+        assert!(signature.starts_with("ed25519:0x"));
+
+        let DidSignature::Ed25519(parsed) = parse_signature(&signature).unwrap() else {
+            panic!("expected an ed25519 signature");
+        };
+        let public = ed25519::Pair::from_string("//Alice", None)
+            .unwrap()
+            .public();
+        assert!(verify_signature(
+            "ed25519",
+            &public.0,
+            hash.as_bytes(),
+            &parsed.0
+        ));
+    }
+}
@@ -0,0 +1,467 @@
+use base58::ToBase58;
+use codec::Encode;
+use subxt::ext::sp_core::crypto::Ss58Codec;
+use subxt::ext::sp_core::H256;
+use subxt::utils::AccountId32;
+
+use crate::kilt::{
+    self,
+    runtime_types::{
+        did::{
+            did_details::{
+                DidDetails, DidEncryptionKey, DidPublicKey, DidSignature, DidVerificationKey,
+            },
+            service_endpoints::DidEndpoint,
+        },
+        sp_core::{bounded::bounded_vec::BoundedVec, ecdsa, ed25519, sr25519},
+    },
+};
+use crate::{fetch_prefix, twox_64_concat, Storage};
+
+pub mod light;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct DidDocument {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    #[serde(rename = "alsoKnownAs", skip_serializing_if = "Vec::is_empty", default)]
+    pub also_known_as: Vec<String>,
+    #[serde(rename = "verificationMethod")]
+    pub verification_method: Vec<VerificationMethod>,
+    pub authentication: Vec<String>,
+    #[serde(
+        rename = "assertionMethod",
+        skip_serializing_if = "Vec::is_empty",
+        default
+    )]
+    pub assertion_method: Vec<String>,
+    #[serde(
+        rename = "capabilityDelegation",
+        skip_serializing_if = "Vec::is_empty",
+        default
+    )]
+    pub capability_delegation: Vec<String>,
+    #[serde(
+        rename = "keyAgreement",
+        skip_serializing_if = "Vec::is_empty",
+        default
+    )]
+    pub key_agreement: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub service: Vec<Service>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct VerificationMethod {
+    pub id: String,
+    pub controller: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(rename = "publicKeyMultibase")]
+    pub public_key_multibase: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Service {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: Vec<String>,
+    #[serde(rename = "serviceEndpoint")]
+    pub service_endpoint: Vec<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct DidResolutionResult {
+    #[serde(rename = "didDocument")]
+    pub did_document: Option<DidDocument>,
+    #[serde(rename = "didDocumentMetadata")]
+    pub did_document_metadata: DidDocumentMetadata,
+    #[serde(rename = "didResolutionMetadata")]
+    pub did_resolution_metadata: DidResolutionMetadata,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct DidDocumentMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deactivated: Option<bool>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct DidResolutionMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub fn did_uri(did: &AccountId32) -> String {
+    format!(
+        "did:kilt:{}",
+        subxt::ext::sp_core::crypto::AccountId32::from(did.0)
+            .to_ss58check_with_version(38u16.into())
+    )
+}
+
+// Encode a public key as multibase (base58btc) with its multicodec prefix.
+pub fn multibase_key(key_type: &str, key: &[u8]) -> String {
+    let codec: &[u8] = match key_type {
+        "ed25519" => &[0xed, 0x01],
+        "sr25519" => &[0xef, 0x01],
+        "ecdsa" => &[0xe7, 0x01],
+        "x25519" => &[0xec, 0x01],
+        _ => &[],
+    };
+    format!("z{}", [codec, key].concat().to_base58())
+}
+
+pub fn public_key(key: &DidPublicKey) -> (&'static str, Vec<u8>) {
+    match key {
+        DidPublicKey::PublicVerificationKey(DidVerificationKey::Sr25519(k)) => {
+            ("sr25519", k.0.to_vec())
+        }
+        DidPublicKey::PublicVerificationKey(DidVerificationKey::Ed25519(k)) => {
+            ("ed25519", k.0.to_vec())
+        }
+        DidPublicKey::PublicVerificationKey(DidVerificationKey::Ecdsa(k)) => {
+            ("ecdsa", k.0.to_vec())
+        }
+        DidPublicKey::PublicEncryptionKey(DidEncryptionKey::X25519(k)) => ("x25519", k.to_vec()),
+    }
+}
+
+pub fn verify_signature(
+    key_type: &str,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    use subxt::ext::sp_core::{ecdsa, ed25519, sr25519, Pair};
+    match key_type {
+        "sr25519" => match (
+            sr25519::Public::try_from(public_key),
+            sr25519::Signature::try_from(signature),
+        ) {
+            (Ok(public), Ok(signature)) => sr25519::Pair::verify(&signature, message, &public),
+            _ => false,
+        },
+        "ed25519" => match (
+            ed25519::Public::try_from(public_key),
+            ed25519::Signature::try_from(signature),
+        ) {
+            (Ok(public), Ok(signature)) => ed25519::Pair::verify(&signature, message, &public),
+            _ => false,
+        },
+        "ecdsa" => match (
+            ecdsa::Public::try_from(public_key),
+            ecdsa::Signature::try_from(signature),
+        ) {
+            (Ok(public), Ok(signature)) => ecdsa::Pair::verify(&signature, message, &public),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// Parse a verification key given as `type:0x...`; keys without a type prefix are sr25519.
+pub fn parse_verification_key(key: &str) -> Result<DidVerificationKey, Box<dyn std::error::Error>> {
+    let (key_type, key) = key.split_once(':').unwrap_or(("sr25519", key));
+    let bytes = hex::decode(key.trim_start_matches("0x").trim())?;
+    match key_type {
+        "sr25519" => Ok(DidVerificationKey::Sr25519(sr25519::Public(
+            bytes.try_into().map_err(|_| "key malformed")?,
+        ))),
+        "ed25519" => Ok(DidVerificationKey::Ed25519(ed25519::Public(
+            bytes.try_into().map_err(|_| "key malformed")?,
+        ))),
+        "ecdsa" => Ok(DidVerificationKey::Ecdsa(ecdsa::Public(
+            bytes.try_into().map_err(|_| "key malformed")?,
+        ))),
+        _ => Err(format!("unknown key type {key_type}").into()),
+    }
+}
+
+// Sign a message with the DID key derived from `seed`.
+pub fn sign(
+    key_type: &str,
+    seed: &str,
+    message: &[u8],
+) -> Result<DidSignature, Box<dyn std::error::Error>> {
+    use subxt::ext::sp_core::{ecdsa, ed25519, sr25519, Pair};
+    match key_type {
+        "sr25519" => Ok(DidSignature::Sr25519(
+            kilt::runtime_types::sp_core::sr25519::Signature(
+                sr25519::Pair::from_string_with_seed(seed, None)
+                    .map_err(|_| "bad seed")?
+                    .0
+                    .sign(message)
+                    .0,
+            ),
+        )),
+        "ed25519" => Ok(DidSignature::Ed25519(
+            kilt::runtime_types::sp_core::ed25519::Signature(
+                ed25519::Pair::from_string_with_seed(seed, None)
+                    .map_err(|_| "bad seed")?
+                    .0
+                    .sign(message)
+                    .0,
+            ),
+        )),
+        "ecdsa" => Ok(DidSignature::Ecdsa(
+            kilt::runtime_types::sp_core::ecdsa::Signature(
+                ecdsa::Pair::from_string_with_seed(seed, None)
+                    .map_err(|_| "bad seed")?
+                    .0
+                    .sign(message)
+                    .0,
+            ),
+        )),
+        _ => Err(format!("unknown key type {key_type}").into()),
+    }
+}
+
+// The public verification key derived from `seed`, i.e. the key `sign` signs with.
+pub fn verification_key(
+    key_type: &str,
+    seed: &str,
+) -> Result<DidVerificationKey, Box<dyn std::error::Error>> {
+    use subxt::ext::sp_core::{ecdsa, ed25519, sr25519, Pair};
+    match key_type {
+        "sr25519" => Ok(DidVerificationKey::Sr25519(
+            kilt::runtime_types::sp_core::sr25519::Public(
+                sr25519::Pair::from_string_with_seed(seed, None)
+                    .map_err(|_| "bad seed")?
+                    .0
+                    .public()
+                    .0,
+            ),
+        )),
+        "ed25519" => Ok(DidVerificationKey::Ed25519(
+            kilt::runtime_types::sp_core::ed25519::Public(
+                ed25519::Pair::from_string_with_seed(seed, None)
+                    .map_err(|_| "bad seed")?
+                    .0
+                    .public()
+                    .0,
+            ),
+        )),
+        "ecdsa" => Ok(DidVerificationKey::Ecdsa(
+            kilt::runtime_types::sp_core::ecdsa::Public(
+                ecdsa::Pair::from_string_with_seed(seed, None)
+                    .map_err(|_| "bad seed")?
+                    .0
+                    .public()
+                    .0,
+            ),
+        )),
+        _ => Err(format!("unknown key type {key_type}").into()),
+    }
+}
+
+// The DID identifier of a full DID created with this authentication key.
+pub fn did_account(key: &DidVerificationKey) -> AccountId32 {
+    use subxt::ext::sp_core::{ecdsa, ed25519, sr25519};
+    use subxt::ext::sp_runtime::{traits::IdentifyAccount, MultiSigner};
+    let signer = match key {
+        DidVerificationKey::Sr25519(k) => MultiSigner::from(sr25519::Public(k.0)),
+        DidVerificationKey::Ed25519(k) => MultiSigner::from(ed25519::Public(k.0)),
+        DidVerificationKey::Ecdsa(k) => MultiSigner::from(ecdsa::Public(k.0)),
+    };
+    signer.into_account().into()
+}
+
+// Parse a signature given as `type:0x...`; signatures without a type prefix are sr25519.
+pub fn parse_signature(signature: &str) -> Result<DidSignature, Box<dyn std::error::Error>> {
+    let (key_type, signature) = signature.split_once(':').unwrap_or(("sr25519", signature));
+    let bytes = hex::decode(signature.trim_start_matches("0x").trim())?;
+    match key_type {
+        "sr25519" => Ok(DidSignature::Sr25519(sr25519::Signature(
+            bytes.try_into().map_err(|_| "signature malformed")?,
+        ))),
+        "ed25519" => Ok(DidSignature::Ed25519(ed25519::Signature(
+            bytes.try_into().map_err(|_| "signature malformed")?,
+        ))),
+        "ecdsa" => Ok(DidSignature::Ecdsa(ecdsa::Signature(
+            bytes.try_into().map_err(|_| "signature malformed")?,
+        ))),
+        _ => Err(format!("unknown key type {key_type}").into()),
+    }
+}
+
+pub fn signature_bytes(signature: &DidSignature) -> (&'static str, &[u8]) {
+    match signature {
+        DidSignature::Sr25519(s) => ("sr25519", &s.0[..]),
+        DidSignature::Ed25519(s) => ("ed25519", &s.0[..]),
+        DidSignature::Ecdsa(s) => ("ecdsa", &s.0[..]),
+    }
+}
+
+// Format a signature as `type:0x...`, the format `parse_signature` reads.
+pub fn signature_string(signature: &DidSignature) -> String {
+    let (key_type, bytes) = signature_bytes(signature);
+    format!("{key_type}:0x{}", hex::encode(bytes))
+}
+
+// Convert a service (with a relative id) into its on-chain representation.
+pub fn did_endpoint(service: &Service) -> DidEndpoint {
+    DidEndpoint {
+        id: BoundedVec(service.id.as_bytes().to_vec()),
+        service_types: BoundedVec(
+            service
+                .type_
+                .iter()
+                .map(|t| BoundedVec(t.as_bytes().to_vec()))
+                .collect(),
+        ),
+        urls: BoundedVec(
+            service
+                .service_endpoint
+                .iter()
+                .map(|u| BoundedVec(u.as_bytes().to_vec()))
+                .collect(),
+        ),
+    }
+}
+
+impl DidDocument {
+    pub fn new(id: &str) -> Self {
+        Self {
+            context: vec![
+                "https://www.w3.org/ns/did/v1".to_string(),
+                "https://w3id.org/security/multikey/v1".to_string(),
+            ],
+            id: id.to_string(),
+            also_known_as: vec![],
+            verification_method: vec![],
+            authentication: vec![],
+            assertion_method: vec![],
+            capability_delegation: vec![],
+            key_agreement: vec![],
+            service: vec![],
+        }
+    }
+
+    // Add a verification method and return its id for use in the relationship lists.
+    pub fn add_key(&mut self, key_id: &str, key_type: &str, key: &[u8]) -> String {
+        let id = format!("{}#{}", self.id, key_id);
+        self.verification_method.push(VerificationMethod {
+            id: id.clone(),
+            controller: self.id.clone(),
+            type_: "Multikey".to_string(),
+            public_key_multibase: multibase_key(key_type, key),
+        });
+        id
+    }
+
+    pub fn add_service(&mut self, service_id: &str, types: Vec<String>, urls: Vec<String>) {
+        self.service.push(Service {
+            id: format!("{}#{}", self.id, service_id),
+            type_: types,
+            service_endpoint: urls,
+        });
+    }
+
+    pub fn from_details(
+        did: &AccountId32,
+        details: &DidDetails,
+        services: &[DidEndpoint],
+        web3_name: Option<String>,
+    ) -> Self {
+        let mut doc = DidDocument::new(&did_uri(did));
+
+        let add_key = |doc: &mut DidDocument, key_id: &H256| {
+            let key = details
+                .public_keys
+                .0
+                .iter()
+                .find(|(id, _)| id == key_id)
+                .map(|(_, key)| public_key(&key.key));
+            key.map(|(key_type, key)| doc.add_key(&format!("{key_id:?}"), key_type, &key))
+        };
+
+        if let Some(id) = add_key(&mut doc, &details.authentication_key) {
+            doc.authentication.push(id);
+        }
+        if let Some(id) = details
+            .attestation_key
+            .and_then(|key_id| add_key(&mut doc, &key_id))
+        {
+            doc.assertion_method.push(id);
+        }
+        if let Some(id) = details
+            .delegation_key
+            .and_then(|key_id| add_key(&mut doc, &key_id))
+        {
+            doc.capability_delegation.push(id);
+        }
+        for key_id in &details.key_agreement_keys.0 {
+            if let Some(id) = add_key(&mut doc, key_id) {
+                doc.key_agreement.push(id);
+            }
+        }
+
+        for service in services {
+            doc.add_service(
+                &String::from_utf8_lossy(&service.id.0),
+                service
+                    .service_types
+                    .0
+                    .iter()
+                    .map(|t| String::from_utf8_lossy(&t.0).to_string())
+                    .collect(),
+                service
+                    .urls
+                    .0
+                    .iter()
+                    .map(|u| String::from_utf8_lossy(&u.0).to_string())
+                    .collect(),
+            );
+        }
+
+        if let Some(name) = web3_name {
+            doc.also_known_as.push(format!("w3n:{name}"));
+        }
+
+        doc
+    }
+}
+
+pub async fn resolve(
+    storage: &Storage,
+    did: &AccountId32,
+) -> Result<DidResolutionResult, Box<dyn std::error::Error>> {
+    let mut result = DidResolutionResult::default();
+
+    if storage
+        .fetch(&kilt::storage().did().did_blacklist(did))
+        .await?
+        .is_some()
+    {
+        result.did_document_metadata.deactivated = Some(true);
+        return Ok(result);
+    }
+
+    let Some(details) = storage.fetch(&kilt::storage().did().did(did)).await? else {
+        result.did_resolution_metadata.error = Some("notFound".to_string());
+        return Ok(result);
+    };
+
+    let services = fetch_prefix::<DidEndpoint>(
+        storage,
+        "Did",
+        "ServiceEndpoints",
+        &twox_64_concat(&did.encode()),
+    )
+    .await?
+    .into_iter()
+    .map(|(_, service)| service)
+    .collect::<Vec<_>>();
+
+    let web3_name = storage
+        .fetch(&kilt::storage().web3_names().names(did))
+        .await?
+        .map(|name| String::from_utf8_lossy(&name.0 .0).to_string());
+
+    result.did_document = Some(DidDocument::from_details(
+        did, &details, &services, web3_name,
+    ));
+    Ok(result)
+}
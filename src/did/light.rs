@@ -0,0 +1,428 @@
+use base58::ToBase58;
+use subxt::ext::sp_core::crypto::Ss58Codec;
+use subxt::utils::AccountId32;
+
+use super::{did_endpoint, DidDocument, Service};
+use crate::kilt::runtime_types::{
+    did::did_details::{DidCreationDetails, DidEncryptionKey},
+    sp_core::bounded::bounded_btree_set::BoundedBTreeSet,
+};
+
+const PREFIX: &str = "did:kilt:light:";
+const SERIALIZATION_VERSION: u8 = 0x00;
+
+// An off-chain DID that carries its keys and services in the identifier:
+// `did:kilt:light:<auth key type><address>[:<details>]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightDid {
+    pub auth_key_type: String,
+    pub auth_key: [u8; 32],
+    pub key_agreement_key: Option<[u8; 32]>,
+    pub services: Vec<Service>,
+}
+
+impl LightDid {
+    pub fn new(
+        auth_key_type: &str,
+        auth_key: [u8; 32],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        match auth_key_type {
+            "sr25519" | "ed25519" => Ok(Self {
+                auth_key_type: auth_key_type.to_string(),
+                auth_key,
+                key_agreement_key: None,
+                services: vec![],
+            }),
+            _ => Err(format!("unsupported light DID key type {auth_key_type}").into()),
+        }
+    }
+
+    pub fn address(&self) -> String {
+        subxt::ext::sp_core::crypto::AccountId32::from(self.auth_key)
+            .to_ss58check_with_version(38u16.into())
+    }
+
+    pub fn uri(&self) -> String {
+        let key_type = if self.auth_key_type == "ed25519" {
+            "01"
+        } else {
+            "00"
+        };
+        let mut uri = format!("{PREFIX}{key_type}{}", self.address());
+        if let Some(details) = self.encode_details() {
+            uri.push(':');
+            uri.push_str(&details);
+        }
+        uri
+    }
+
+    pub fn parse(uri: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let uri = uri.split('#').next().unwrap_or_default();
+        let rest = uri.strip_prefix(PREFIX).ok_or("not a light DID")?;
+        let (identifier, details) = match rest.split_once(':') {
+            Some((identifier, details)) => (identifier, Some(details)),
+            None => (rest, None),
+        };
+        if identifier.len() < 3 {
+            return Err("invalid light DID identifier".into());
+        }
+        let auth_key_type = match &identifier[..2] {
+            "00" => "sr25519",
+            "01" => "ed25519",
+            other => return Err(format!("unknown light DID key type {other}").into()),
+        };
+        let (account, version) =
+            subxt::ext::sp_core::crypto::AccountId32::from_ss58check_with_version(&identifier[2..])
+                .map_err(|_| "invalid light DID address")?;
+        if u16::from(version) != 38 {
+            return Err("light DID address is not a KILT address".into());
+        }
+        let mut did = LightDid::new(auth_key_type, account.into())?;
+        if let Some(details) = details {
+            did.decode_details(details)?;
+        }
+        Ok(did)
+    }
+
+    // The full DID the light DID migrates to: same identifier, stored on chain.
+    pub fn full_did(&self) -> String {
+        format!("did:kilt:{}", self.address())
+    }
+
+    pub fn document(&self) -> DidDocument {
+        let mut doc = DidDocument::new(&self.uri());
+        let id = doc.add_key("authentication", &self.auth_key_type, &self.auth_key);
+        doc.authentication.push(id);
+        if let Some(key) = &self.key_agreement_key {
+            let id = doc.add_key("encryption", "x25519", key);
+            doc.key_agreement.push(id);
+        }
+        for service in &self.services {
+            doc.add_service(
+                &service.id,
+                service.type_.clone(),
+                service.service_endpoint.clone(),
+            );
+        }
+        doc.also_known_as.push(self.full_did());
+        doc
+    }
+
+    // Details for the `did.create` call that moves this light DID on chain.
+    pub fn creation_details(&self, submitter: AccountId32) -> DidCreationDetails {
+        DidCreationDetails {
+            did: AccountId32(self.auth_key),
+            submitter,
+            new_key_agreement_keys: BoundedBTreeSet(
+                self.key_agreement_key
+                    .iter()
+                    .map(|key| DidEncryptionKey::X25519(*key))
+                    .collect(),
+            ),
+            new_attestation_key: None,
+            new_delegation_key: None,
+            new_service_details: self.services.iter().map(did_endpoint).collect(),
+        }
+    }
+
+    fn encode_details(&self) -> Option<String> {
+        let mut details = vec![];
+        if let Some(key) = &self.key_agreement_key {
+            details.push((
+                "e".to_string(),
+                Cbor::Map(vec![
+                    ("publicKey".to_string(), Cbor::Bytes(key.to_vec())),
+                    ("type".to_string(), Cbor::Text("x25519".to_string())),
+                ]),
+            ));
+        }
+        if !self.services.is_empty() {
+            let services = self
+                .services
+                .iter()
+                .map(|service| {
+                    Cbor::Map(vec![
+                        ("id".to_string(), Cbor::Text(service.id.clone())),
+                        (
+                            "type".to_string(),
+                            Cbor::Array(service.type_.iter().cloned().map(Cbor::Text).collect()),
+                        ),
+                        (
+                            "serviceEndpoint".to_string(),
+                            Cbor::Array(
+                                service
+                                    .service_endpoint
+                                    .iter()
+                                    .cloned()
+                                    .map(Cbor::Text)
+                                    .collect(),
+                            ),
+                        ),
+                    ])
+                })
+                .collect();
+            details.push(("s".to_string(), Cbor::Array(services)));
+        }
+        if details.is_empty() {
+            return None;
+        }
+        let mut bytes = vec![SERIALIZATION_VERSION];
+        Cbor::Map(details).encode(&mut bytes);
+        Some(format!("z{}", bytes.to_base58()))
+    }
+
+    fn decode_details(&mut self, details: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let details = details
+            .strip_prefix('z')
+            .ok_or("light DID details are not base58 multibase encoded")?;
+        let bytes = decode_base58(details).ok_or("invalid base58 in light DID details")?;
+        let (version, mut data) = bytes.split_first().ok_or("empty light DID details")?;
+        if *version != SERIALIZATION_VERSION {
+            return Err(format!("unsupported light DID serialization version {version}").into());
+        }
+        let Cbor::Map(entries) = Cbor::decode(&mut data)? else {
+            return Err("light DID details are not a map".into());
+        };
+        for (key, value) in entries {
+            match key.as_str() {
+                "e" => {
+                    let public_key = value.get("publicKey").ok_or("missing key agreement key")?;
+                    self.key_agreement_key = Some(
+                        public_key
+                            .as_bytes()
+                            .ok_or("invalid key agreement key")?
+                            .try_into()
+                            .map_err(|_| "invalid key agreement key length")?,
+                    );
+                }
+                "s" => {
+                    let Cbor::Array(services) = value else {
+                        return Err("light DID services are not a list".into());
+                    };
+                    for service in services {
+                        self.services.push(Service {
+                            id: service
+                                .get("id")
+                                .and_then(Cbor::as_text)
+                                .ok_or("missing service id")?
+                                .trim_start_matches('#')
+                                .to_string(),
+                            type_: service
+                                .get("type")
+                                .or(service.get("types"))
+                                .map(Cbor::texts)
+                                .unwrap_or_default(),
+                            service_endpoint: service
+                                .get("serviceEndpoint")
+                                .or(service.get("urls"))
+                                .map(Cbor::texts)
+                                .unwrap_or_default(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+// The subset of CBOR used by light DID details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Cbor {
+    Uint(u64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Cbor>),
+    Map(Vec<(String, Cbor)>),
+}
+
+impl Cbor {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Cbor::Uint(n) => write_header(out, 0, *n),
+            Cbor::Bytes(bytes) => {
+                write_header(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            Cbor::Text(text) => {
+                write_header(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            Cbor::Array(items) => {
+                write_header(out, 4, items.len() as u64);
+                for item in items {
+                    item.encode(out);
+                }
+            }
+            Cbor::Map(entries) => {
+                write_header(out, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    Cbor::Text(key.clone()).encode(out);
+                    value.encode(out);
+                }
+            }
+        }
+    }
+
+    fn decode(data: &mut &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let (major, arg) = read_header(data)?;
+        match major {
+            0 => Ok(Cbor::Uint(arg)),
+            2 => Ok(Cbor::Bytes(take(data, arg)?.to_vec())),
+            3 => Ok(Cbor::Text(String::from_utf8(take(data, arg)?.to_vec())?)),
+            4 => (0..arg)
+                .map(|_| Cbor::decode(data))
+                .collect::<Result<_, _>>()
+                .map(Cbor::Array),
+            5 => (0..arg)
+                .map(|_| match Cbor::decode(data)? {
+                    Cbor::Text(key) => Ok((key, Cbor::decode(data)?)),
+                    _ => Err("unsupported CBOR map key".into()),
+                })
+                .collect::<Result<_, Box<dyn std::error::Error>>>()
+                .map(Cbor::Map),
+            // Tags (e.g. typed arrays) wrap the actual value.
+            6 => Cbor::decode(data),
+            _ => Err(format!("unsupported CBOR major type {major}").into()),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&Cbor> {
+        match self {
+            Cbor::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_text(&self) -> Option<&str> {
+        match self {
+            Cbor::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn as_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Cbor::Bytes(bytes) => Some(bytes.clone()),
+            Cbor::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    Cbor::Uint(n) => u8::try_from(*n).ok(),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    fn texts(&self) -> Vec<String> {
+        match self {
+            Cbor::Text(text) => vec![text.clone()],
+            Cbor::Array(items) => items
+                .iter()
+                .filter_map(|item| item.as_text().map(str::to_string))
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+fn write_header(out: &mut Vec<u8>, major: u8, arg: u64) {
+    let major = major << 5;
+    if arg < 24 {
+        out.push(major | arg as u8);
+    } else if arg <= u8::MAX as u64 {
+        out.push(major | 24);
+        out.push(arg as u8);
+    } else if arg <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(arg as u16).to_be_bytes());
+    } else if arg <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(arg as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&arg.to_be_bytes());
+    }
+}
+
+fn read_header(data: &mut &[u8]) -> Result<(u8, u64), Box<dyn std::error::Error>> {
+    let first = take(data, 1)?[0];
+    let arg = match first & 0x1f {
+        n @ 0..=23 => n as u64,
+        24 => take(data, 1)?[0] as u64,
+        25 => u16::from_be_bytes(take(data, 2)?.try_into()?) as u64,
+        26 => u32::from_be_bytes(take(data, 4)?.try_into()?) as u64,
+        27 => u64::from_be_bytes(take(data, 8)?.try_into()?),
+        _ => return Err("unsupported CBOR length encoding".into()),
+    };
+    Ok((first >> 5, arg))
+}
+
+fn take<'a>(data: &mut &'a [u8], len: u64) -> Result<&'a [u8], Box<dyn std::error::Error>> {
+    let len = usize::try_from(len)?;
+    if data.len() < len {
+        return Err("unexpected end of CBOR data".into());
+    }
+    let (head, rest) = data.split_at(len);
+    *data = rest;
+    Ok(head)
+}
+
+// base58's decoder is limited to 132 bytes, which is not enough for light DIDs with services.
+fn decode_base58(input: &str) -> Option<Vec<u8>> {
+    const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    // little endian digits of the decoded number
+    let mut bytes: Vec<u8> = vec![];
+    for c in input.bytes() {
+        let mut carry = ALPHABET.iter().position(|a| *a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = input.bytes().take_while(|c| *c == b'1').count();
+    let mut decoded = vec![0; zeros];
+    decoded.extend(bytes.iter().rev());
+    Some(decoded)
+}
+
+mod test {
+    #[test]
+    fn test_light_did_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        use super::*;
+
+        let mut did = LightDid::new("ed25519", [1u8; 32])?;
+        assert!(did.uri().starts_with("did:kilt:light:01"));
+        assert_eq!(LightDid::parse(&did.uri())?, did);
+
+        did.key_agreement_key = Some([2u8; 32]);
+        did.services.push(Service {
+            id: "my-service".to_string(),
+            type_: vec!["KiltPublishedCredentialCollectionV1".to_string()],
+            service_endpoint: vec!["https://example.com/credentials".to_string()],
+        });
+        assert_eq!(LightDid::parse(&did.uri())?, did);
+        assert_eq!(
+            LightDid::parse(&format!("{}#authentication", did.uri()))?,
+            did
+        );
+
+        let doc = did.document();
+        assert_eq!(doc.authentication.len(), 1);
+        assert_eq!(doc.key_agreement.len(), 1);
+        assert_eq!(doc.service[0].id, format!("{}#my-service", did.uri()));
+        assert_eq!(doc.also_known_as, vec![did.full_did()]);
+
+        let bytes = [0, 0, 1, 2, 255];
+        assert_eq!(decode_base58(&bytes.to_base58()), Some(bytes.to_vec()));
+
+        Ok(())
+    }
+}
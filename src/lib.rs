@@ -6,7 +6,11 @@ use subxt::ext::sp_core::H256;
 use subxt::{tx::TxPayload, utils::AccountId32, OnlineClient};
 
 pub mod credential;
+pub mod ctype;
+pub mod delegation;
+pub mod did;
 pub mod kilt;
+pub mod linking;
 #[derive(Debug, Clone)]
 pub struct AccountIdParser;
 
@@ -30,6 +34,61 @@ impl clap::builder::TypedValueParser for AccountIdParser {
     }
 }
 
+// A DID given directly or as `w3n:<name>`. Web3 names are resolved with `resolve_did` once the
+// command is connected.
+#[derive(Debug, Clone)]
+pub enum DidArg {
+    Account(AccountId32),
+    Web3Name(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct DidParser;
+
+impl clap::builder::TypedValueParser for DidParser {
+    type Value = DidArg;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        if let Some(name) = value.to_str().and_then(|val| val.strip_prefix("w3n:")) {
+            return Ok(DidArg::Web3Name(name.to_string()));
+        }
+        clap::builder::TypedValueParser::parse_ref(&AccountIdParser, cmd, arg, value)
+            .map(DidArg::Account)
+    }
+}
+
+// Resolve a DID argument, looking up web3 names at the block selected with `--at`.
+pub async fn resolve_did(
+    matches: &clap::ArgMatches,
+    cli: &OnlineClient<KiltConfig>,
+    did: &DidArg,
+) -> Result<AccountId32, Box<dyn std::error::Error>> {
+    match did {
+        DidArg::Account(did) => Ok(did.clone()),
+        DidArg::Web3Name(name) => web3_name_owner(&storage_at(matches, cli).await?, name)
+            .await?
+            .ok_or_else(|| format!("web3 name {name} not found").into()),
+    }
+}
+
+pub async fn web3_name_owner(
+    storage: &Storage,
+    name: &str,
+) -> Result<Option<AccountId32>, Box<dyn std::error::Error>> {
+    use kilt::runtime_types::{
+        pallet_web3_names::web3_name::AsciiWeb3Name, sp_core::bounded::bounded_vec::BoundedVec,
+    };
+    let addr = kilt::storage()
+        .web3_names()
+        .owner(AsciiWeb3Name(BoundedVec(name.as_bytes().to_vec())));
+    Ok(storage.fetch(&addr).await?.map(|ownership| ownership.owner))
+}
+
 #[derive(Debug, Clone)]
 pub struct BalanceParser;
 
@@ -116,6 +175,125 @@ impl clap::builder::TypedValueParser for CallParser {
     }
 }
 
+// Parses an x25519 key agreement key given as `x25519:0x...` (the type prefix is optional).
+#[derive(Debug, Clone)]
+pub struct KeyAgreementKeyParser;
+
+impl clap::builder::TypedValueParser for KeyAgreementKeyParser {
+    type Value = [u8; 32];
+
+    fn parse_ref(
+        &self,
+        _cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let val = value
+            .to_os_string()
+            .into_string()
+            .map_err(|_| clap::Error::new(Format))?;
+        let val = val.trim_start_matches("x25519:").trim_start_matches("0x").trim();
+        let bytes: [u8; 32] = hex::decode(val)
+            .map_err(|_| clap::Error::new(Format))?
+            .try_into()
+            .map_err(|_| clap::Error::new(Format))?;
+        Ok(bytes)
+    }
+}
+
+// An account that can be linked to a DID.
+#[derive(Debug, Clone)]
+pub enum LinkableAccount {
+    Ethereum([u8; 20]),
+    Substrate(AccountId32),
+}
+
+impl From<&LinkableAccount>
+    for kilt::runtime_types::pallet_did_lookup::linkable_account::LinkableAccountId
+{
+    fn from(account: &LinkableAccount) -> Self {
+        use kilt::runtime_types::pallet_did_lookup::account::AccountId20;
+        match account {
+            LinkableAccount::Ethereum(address) => Self::AccountId20(AccountId20(*address)),
+            LinkableAccount::Substrate(account) => Self::AccountId32(account.clone()),
+        }
+    }
+}
+
+// Parses a linkable account given as ss58 address or `0x...` ethereum address.
+#[derive(Debug, Clone)]
+pub struct LinkableAccountParser;
+
+impl clap::builder::TypedValueParser for LinkableAccountParser {
+    type Value = LinkableAccount;
+
+    fn parse_ref(
+        &self,
+        _cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let val = value
+            .to_os_string()
+            .into_string()
+            .map_err(|_| clap::Error::new(InvalidValue))?;
+        if let Some(address) = val.strip_prefix("0x") {
+            let bytes: [u8; 20] = hex::decode(address.trim())
+                .map_err(|_| clap::Error::new(Format))?
+                .try_into()
+                .map_err(|_| clap::Error::new(Format))?;
+            return Ok(LinkableAccount::Ethereum(bytes));
+        }
+        let account = AccountId32::from_str(&val).map_err(|_| clap::Error::new(Format))?;
+        Ok(LinkableAccount::Substrate(account))
+    }
+}
+
+// Parses a DID service given as `id,type,url`.
+#[derive(Debug, Clone)]
+pub struct ServiceParser;
+
+impl clap::builder::TypedValueParser for ServiceParser {
+    type Value = did::Service;
+
+    fn parse_ref(
+        &self,
+        _cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let val = value
+            .to_os_string()
+            .into_string()
+            .map_err(|_| clap::Error::new(Format))?;
+        let mut parts = val.splitn(3, ',');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(id), Some(type_), Some(url)) if !id.is_empty() => Ok(did::Service {
+                id: id.trim_start_matches('#').to_string(),
+                type_: vec![type_.to_string()],
+                service_endpoint: vec![url.to_string()],
+            }),
+            _ => Err(clap::Error::new(InvalidValue)),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct ChainInfo {
+    #[serde(rename = "genesisHash")]
+    pub genesis_hash: H256,
+    #[serde(rename = "specVersion")]
+    pub spec_version: u32,
+    #[serde(rename = "transactionVersion")]
+    pub transaction_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(rename = "blockHash", skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<H256>,
+    #[serde(rename = "blockNumber", skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RawCall {
     pub call: Vec<u8>,
@@ -143,6 +321,30 @@ pub fn unwrap_or_stdin(data: Option<String>) -> Result<String, Box<dyn std::erro
     }
 }
 
+pub fn output_json(matches: &clap::ArgMatches) -> bool {
+    matches
+        .get_one::<String>("output")
+        .map(|output| output == "json")
+        .unwrap_or(false)
+}
+
+pub fn print_output(
+    matches: &clap::ArgMatches,
+    text: impl std::fmt::Display,
+    json: serde_json::Value,
+) {
+    if output_json(matches) {
+        println!("{json}");
+    } else {
+        println!("{text}");
+    }
+}
+
+pub fn print_call(matches: &clap::ArgMatches, call: &[u8]) {
+    let call = format!("0x{}", hex::encode(call));
+    print_output(matches, &call, serde_json::json!({ "call": call }));
+}
+
 pub fn format_balance(b: u128) -> String {
     if b < 1_000_000_000_000_000 {
         let d = b / 1_000_000_000_000;
@@ -163,6 +365,30 @@ pub fn format_balance(b: u128) -> String {
     }
 }
 
+pub fn format_dispatch_error(
+    err: &kilt::runtime_types::sp_runtime::DispatchError,
+    metadata: &subxt::Metadata,
+) -> String {
+    use kilt::runtime_types::sp_runtime::DispatchError;
+    match err {
+        DispatchError::Module(module_error) => {
+            let Some(pallet) = metadata.pallet_by_index(module_error.index) else {
+                return format!("unknown pallet error {module_error:?}");
+            };
+            match pallet.error_variant_by_index(module_error.error[0]) {
+                Some(variant) => format!(
+                    "{}.{}: {}",
+                    pallet.name(),
+                    variant.name,
+                    variant.docs.join(" ")
+                ),
+                None => format!("{}: unknown error {module_error:?}", pallet.name()),
+            }
+        }
+        _ => format!("{err:?}"),
+    }
+}
+
 pub async fn connect(
     matches: &clap::ArgMatches,
 ) -> Result<OnlineClient<KiltConfig>, Box<dyn std::error::Error>> {
@@ -174,3 +400,152 @@ pub async fn connect(
     };
     Ok(OnlineClient::<KiltConfig>::from_url(endpoint_url).await?)
 }
+
+pub type Storage = subxt::storage::Storage<KiltConfig, OnlineClient<KiltConfig>>;
+
+// A block given by its number or hash, as taken by the global `--at` option.
+#[derive(Debug, Clone)]
+pub enum BlockRef {
+    Number(u64),
+    Hash(H256),
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockRefParser;
+
+impl clap::builder::TypedValueParser for BlockRefParser {
+    type Value = BlockRef;
+
+    fn parse_ref(
+        &self,
+        _cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let val = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(Format))?
+            .trim();
+        if let Some(hash) = val.strip_prefix("0x") {
+            let bytes: [u8; 32] = hex::decode(hash)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| clap::Error::raw(InvalidValue, "invalid block hash\n"))?;
+            return Ok(BlockRef::Hash(H256(bytes)));
+        }
+        val.parse().map(BlockRef::Number).map_err(|_| {
+            clap::Error::raw(InvalidValue, "expected a block number or a 0x block hash\n")
+        })
+    }
+}
+
+// Hash of the block selected with the global `--at` option, None if it is not given.
+pub async fn block_at(
+    matches: &clap::ArgMatches,
+    cli: &OnlineClient<KiltConfig>,
+) -> Result<Option<H256>, Box<dyn std::error::Error>> {
+    match matches.get_one::<BlockRef>("at") {
+        Some(BlockRef::Hash(hash)) => Ok(Some(*hash)),
+        Some(BlockRef::Number(number)) => Ok(Some(
+            cli.rpc()
+                .block_hash(Some((*number).into()))
+                .await?
+                .ok_or_else(|| format!("block {number} not found"))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+// Storage at the block selected with the global `--at` option, or at the latest block if it is
+// not given.
+pub async fn storage_at(
+    matches: &clap::ArgMatches,
+    cli: &OnlineClient<KiltConfig>,
+) -> Result<Storage, Box<dyn std::error::Error>> {
+    match block_at(matches, cli).await? {
+        Some(hash) => Ok(cli.storage().at(hash)),
+        None => Ok(cli.storage().at_latest().await?),
+    }
+}
+
+pub fn twox_64_concat(key: &[u8]) -> Vec<u8> {
+    [&subxt::ext::sp_core::twox_64(key)[..], key].concat()
+}
+
+pub fn blake2_128_concat(key: &[u8]) -> Vec<u8> {
+    [&subxt::ext::sp_core::blake2_128(key)[..], key].concat()
+}
+
+pub fn ss58(account: &AccountId32) -> String {
+    use subxt::ext::sp_core::crypto::Ss58Codec;
+    subxt::ext::sp_core::crypto::AccountId32::from(account.0)
+        .to_ss58check_with_version(38u16.into())
+}
+
+pub fn storage_prefix(pallet: &str, entry: &str) -> Vec<u8> {
+    [
+        &subxt::ext::sp_core::twox_128(pallet.as_bytes())[..],
+        &subxt::ext::sp_core::twox_128(entry.as_bytes())[..],
+    ]
+    .concat()
+}
+
+// Fetch up to `count` entries whose storage keys start with `prefix`, beginning after `start_key`.
+// Returns every full storage key together with its decoded value; the values are fetched
+// concurrently.
+pub async fn fetch_page<V: codec::Decode>(
+    storage: &Storage,
+    prefix: &[u8],
+    count: u32,
+    start_key: Option<&[u8]>,
+) -> Result<Vec<(Vec<u8>, Option<V>)>, Box<dyn std::error::Error>> {
+    let requests = storage
+        .fetch_keys(prefix, count, start_key)
+        .await?
+        .into_iter()
+        .map(|key| {
+            let storage = storage.clone();
+            tokio::spawn(async move {
+                let value = storage.fetch_raw(&key.0).await;
+                (key.0, value)
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut entries = vec![];
+    for request in requests {
+        let (key, value) = request.await?;
+        let value = match value? {
+            Some(value) => Some(V::decode(&mut &value[..])?),
+            None => None,
+        };
+        entries.push((key, value));
+    }
+    Ok(entries)
+}
+
+// Fetch all entries of a storage double map that share the given (already hashed) first key.
+// Returns the remaining, still hashed, key bytes together with the decoded value.
+pub async fn fetch_prefix<V: codec::Decode>(
+    storage: &Storage,
+    pallet: &str,
+    entry: &str,
+    first_key: &[u8],
+) -> Result<Vec<(Vec<u8>, V)>, Box<dyn std::error::Error>> {
+    let prefix = [&storage_prefix(pallet, entry)[..], first_key].concat();
+
+    let mut entries = vec![];
+    let mut start_key: Option<Vec<u8>> = None;
+    loop {
+        let page = fetch_page::<V>(storage, &prefix, 100, start_key.as_deref()).await?;
+        let Some((last, _)) = page.last() else {
+            break;
+        };
+        start_key = Some(last.clone());
+        entries.extend(
+            page.into_iter().filter_map(|(key, value)| {
+                value.map(|value| (key[prefix.len()..].to_vec(), value))
+            }),
+        );
+    }
+    Ok(entries)
+}
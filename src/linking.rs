@@ -0,0 +1,143 @@
+use bip39::{Language, Mnemonic, Seed};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use subxt::ext::sp_core::hashing::keccak_256;
+use subxt::utils::AccountId32;
+
+use crate::did::did_uri;
+use crate::LinkableAccount;
+
+pub const DEFAULT_ETHEREUM_PATH: &str = "m/44'/60'/0'/0/0";
+
+// The message an account has to sign to be linked to a DID.
+pub fn challenge(did: &AccountId32, expiration: u64) -> String {
+    format!(
+        "Publicly link the signing address to {} before block number {}",
+        did_uri(did),
+        expiration
+    )
+}
+
+// Substrate signers sign the challenge wrapped in `<Bytes>` tags (like polkadot-js does).
+pub fn wrap_bytes(message: &[u8]) -> Vec<u8> {
+    [b"<Bytes>", message, b"</Bytes>"].concat()
+}
+
+// The bytes the account has to sign. Ethereum wallets add their own prefix in personal_sign.
+pub fn payload(account: &LinkableAccount, did: &AccountId32, expiration: u64) -> Vec<u8> {
+    let challenge = challenge(did, expiration);
+    match account {
+        LinkableAccount::Ethereum(_) => challenge.into_bytes(),
+        LinkableAccount::Substrate(_) => wrap_bytes(challenge.as_bytes()),
+    }
+}
+
+// The EIP-191 (personal_sign) hash of a message.
+pub fn ethereum_message_hash(message: &[u8]) -> [u8; 32] {
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+    keccak_256(&[prefix.as_bytes(), message].concat())
+}
+
+// A secp256k1 key used to sign as an ethereum account.
+pub struct EthereumKey(libsecp256k1::SecretKey);
+
+impl EthereumKey {
+    // Parse a `0x...` private key or derive the key from a BIP-39 mnemonic along `path`.
+    pub fn from_secret(secret: &str, path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        if let Some(key) = secret.trim().strip_prefix("0x") {
+            let key = libsecp256k1::SecretKey::parse_slice(&hex::decode(key)?)
+                .map_err(|_| "invalid private key")?;
+            return Ok(Self(key));
+        }
+        let mnemonic = Mnemonic::from_phrase(secret.trim(), Language::English)?;
+        Self::derive(Seed::new(&mnemonic, "").as_bytes(), path)
+    }
+
+    // BIP-32 derivation of a private key from a seed.
+    fn derive(seed: &[u8], path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let hmac = |key: &[u8], data: &[u8]| {
+            let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("hmac takes any key size");
+            mac.update(data);
+            mac.finalize().into_bytes()
+        };
+        let invalid = |_| "invalid derived key";
+
+        let out = hmac(b"Bitcoin seed", seed);
+        let mut key = libsecp256k1::SecretKey::parse_slice(&out[..32]).map_err(invalid)?;
+        let mut chain_code = out[32..].to_vec();
+
+        let components = path
+            .strip_prefix('m')
+            .ok_or_else(|| format!("invalid derivation path {path}"))?
+            .split('/')
+            .filter(|c| !c.is_empty());
+        for component in components {
+            let (index, hardened) = match component.strip_suffix('\'') {
+                Some(index) => (index, true),
+                None => (component, false),
+            };
+            let mut index: u32 = index
+                .parse()
+                .map_err(|_| format!("invalid derivation path {path}"))?;
+            let data = if hardened {
+                index |= 0x8000_0000;
+                [&[0u8][..], &key.serialize(), &index.to_be_bytes()].concat()
+            } else {
+                let public = libsecp256k1::PublicKey::from_secret_key(&key);
+                [&public.serialize_compressed()[..], &index.to_be_bytes()].concat()
+            };
+            let out = hmac(&chain_code, &data);
+            let tweak = libsecp256k1::SecretKey::parse_slice(&out[..32]).map_err(invalid)?;
+            key.tweak_add_assign(&tweak).map_err(invalid)?;
+            chain_code = out[32..].to_vec();
+        }
+        Ok(Self(key))
+    }
+
+    pub fn address(&self) -> [u8; 20] {
+        let public = libsecp256k1::PublicKey::from_secret_key(&self.0).serialize();
+        keccak_256(&public[1..])[12..]
+            .try_into()
+            .expect("keccak output is 32 bytes")
+    }
+
+    // Sign a message like personal_sign does, returning `r ++ s ++ v` with `v` being 27 or 28.
+    pub fn sign(&self, message: &[u8]) -> [u8; 65] {
+        let message = libsecp256k1::Message::parse(&ethereum_message_hash(message));
+        let (signature, recovery_id) = libsecp256k1::sign(&message, &self.0);
+        let mut out = [0u8; 65];
+        out[..64].copy_from_slice(&signature.serialize());
+        out[64] = recovery_id.serialize() + 27;
+        out
+    }
+}
+
+mod test {
+    #[test]
+    fn test_ethereum_key() {
+        use super::*;
+        let key = EthereumKey::from_secret(
+            "test test test test test test test test test test test junk",
+            DEFAULT_ETHEREUM_PATH,
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(key.0.serialize()),
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        );
+        assert_eq!(
+            hex::encode(key.address()),
+            "f39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        );
+
+        let signature = key.sign(b"hello");
+        let message = libsecp256k1::Message::parse(&ethereum_message_hash(b"hello"));
+        let recovered = libsecp256k1::recover(
+            &message,
+            &libsecp256k1::Signature::parse_standard_slice(&signature[..64]).unwrap(),
+            &libsecp256k1::RecoveryId::parse_rpc(signature[64]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(recovered, libsecp256k1::PublicKey::from_secret_key(&key.0));
+    }
+}